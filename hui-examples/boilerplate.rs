@@ -1,3 +1,4 @@
+use std::time::Instant;
 use glam::{UVec2, Vec2};
 use glium::{Surface, backend::glutin::SimpleWindowBuilder};
 use winit::{
@@ -43,6 +44,7 @@ pub fn ui<T>(
   let mut backend = GliumUiRenderer::new(&display);
 
   let mut result = init(&mut hui);
+  let mut last_frame = Instant::now();
 
   event_loop.run(|event, window_target| {
     window.request_redraw();
@@ -60,6 +62,10 @@ pub fn ui<T>(
           let mut frame = display.draw();
           frame.clear_color_srgb(0.5, 0.5, 0.5, 1.);
 
+          let now = Instant::now();
+          hui.advance_time((now - last_frame).as_secs_f32());
+          last_frame = now;
+
           hui.begin_frame();
 
           let size = UVec2::from(display.get_framebuffer_dimensions()).as_vec2();
@@ -48,22 +48,27 @@ ui_main!(
           .with_text_size(16.)
           .add_child(ui);
         Break.add_child(ui);
-        Slider::new(*counter as f32 / 100.)
+        Slider::new("absolute_slider", *counter as f32)
           .with_size(size!(66%, 20))
+          .with_range(0., 100.)
+          .with_step(1.)
           .on_change(|x| {
-            CounterSignal::ChangeValue((x * 100.).round() as u32)
+            CounterSignal::ChangeValue(x.round() as u32)
           })
           .add_child(ui);
         Break.add_child(ui);
-        Text::new("Relative tracking slider (Experimental):")
+        Text::new("Relative tracking slider with steps and tick marks:")
           .with_text_size(16.)
           .add_child(ui);
         Break.add_child(ui);
-        Slider::new(*counter as f32 / 100.)
+        Slider::new("relative_slider", *counter as f32)
           .with_size(size!(66%, 20))
+          .with_range(0., 100.)
+          .with_step(10.)
+          .with_tick((0.8, 0.8, 0.8))
           .with_follow_mode(hui::element::slider::SliderFollowMode::Relative)
           .on_change(|x| {
-            CounterSignal::ChangeValue((x * 100.).round() as u32)
+            CounterSignal::ChangeValue(x.round() as u32)
           })
           .add_child(ui);
         Break.add_child(ui);
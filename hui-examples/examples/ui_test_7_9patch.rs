@@ -83,7 +83,7 @@ ui_main!(
           .with_color(color::BLACK)
           .with_text_size(32.)
           .add_child(ui);
-        Slider::new(*value)
+        Slider::new("slider", *value)
           .with_size(size!(50%, 30))
           .with_track_height(1.)
           .with_handle_size((20., 1.))
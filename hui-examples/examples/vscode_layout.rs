@@ -7,6 +7,7 @@ use hui::{
     container::Container,
     frame_view::FrameView,
     image::Image,
+    split_pane::SplitPane,
     text::Text,
     UiElementExt
   },
@@ -84,21 +85,51 @@ ui_main!(
               .with_frame(color::rgb_hex(0x2d2d30))
               .add_child(ui);
 
-            // Explorer pane:
-            Container::default()
-              .with_size(size!(200, 100%))
-              .with_padding((15., 8.))
-              .with_background(color::rgb_hex(0x262526))
-              .with_children(|ui| {
-                Text::new("EXPLORER")
-                  .add_child(ui);
-              })
-              .add_child(ui);
-
-            // "Code" pane
-            Container::default()
+            SplitPane::new("explorer_split")
               .with_size(size!(100%=, 100%))
-              .with_background(color::rgb_hex(0x1f1e1f))
+              .with_default_ratio(0.25)
+              .with_min_first(100.)
+              .with_min_second(200.)
+              .with_divider_width(3.)
+              .with_divider(color::rgb_hex(0x2d2d30))
+              // Explorer pane:
+              .with_first(
+                Container::default()
+                  .with_size(size!(100%))
+                  .with_padding((15., 8.))
+                  .with_background(color::rgb_hex(0x262526))
+                  .with_children(|ui| {
+                    Text::new("EXPLORER")
+                      .add_child(ui);
+                  })
+              )
+              .with_second(
+                SplitPane::new("panel_split")
+                  .with_direction(Direction::Vertical)
+                  .with_default_ratio(0.7)
+                  .with_min_first(100.)
+                  .with_min_second(50.)
+                  .with_divider_width(3.)
+                  .with_divider(color::rgb_hex(0x2d2d30))
+                  // "Code" pane
+                  .with_first(
+                    Container::default()
+                      .with_size(size!(100%))
+                      .with_background(color::rgb_hex(0x1f1e1f))
+                  )
+                  // Terminal panel
+                  .with_second(
+                    Container::default()
+                      .with_size(size!(100%))
+                      .with_padding((15., 8.))
+                      .with_background(color::rgb_hex(0x1f1e1f))
+                      .with_children(|ui| {
+                        Text::new("TERMINAL")
+                          .with_text_size(13.)
+                          .add_child(ui);
+                      })
+                  )
+              )
               .add_child(ui);
           })
           .add_child(ui);
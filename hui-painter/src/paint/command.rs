@@ -13,6 +13,12 @@ pub use transform::PaintTransform;
 mod rectangle;
 pub use rectangle::PaintRectangle;
 
+mod clip;
+pub use clip::PaintClip;
+
+mod arc;
+pub use arc::PaintArc;
+
 pub mod text;
 
 pub trait PaintCommand {
@@ -0,0 +1,156 @@
+use core::{f32::consts::TAU, hash::Hasher, num::NonZeroU16};
+use glam::{vec2, Vec2, Vec4};
+use hui_shared::{color, rect::Rect};
+use crate::{
+  paint::{
+    buffer::{PaintBuffer, Vertex},
+    command::PaintCommand,
+  },
+  util::{hash_vec2, hash_vec4},
+  PainterInstance,
+};
+
+/// Calculate the number of points based on the radius and sweep angle
+fn point_count(radius: f32, sweep: f32) -> NonZeroU16 {
+  //Increase for higher quality
+  const VTX_PER_ARC_LENGTH_PIXEL: f32 = 0.25;
+  NonZeroU16::new(
+    (radius * sweep.abs() * VTX_PER_ARC_LENGTH_PIXEL).round().min(u16::MAX as f32 - 2.) as u16 + 2
+  ).unwrap()
+}
+
+/// Arc (ring segment) or pie (circle sector)
+///
+/// The arc is drawn inside of the `[0, 2 * radius]` box, centered at `(radius, radius)`
+pub struct PaintArc {
+  /// Color of the arc.
+  pub color: Vec4,
+
+  /// Outer radius of the arc.
+  pub radius: f32,
+
+  /// Inner radius of the arc.
+  ///
+  /// If set to zero, a pie segment will be drawn instead.
+  pub inner_radius: f32,
+
+  /// Start angle in radians, measured clockwise from the top (12 o'clock).
+  pub start_angle: f32,
+
+  /// Sweep angle in radians, clockwise if positive.
+  ///
+  /// Values outside of the `[-TAU, TAU]` range will be clamped.
+  pub sweep_angle: f32,
+
+  /// Point count override.
+  ///
+  /// If not set, it will be calculated based on the radius and sweep angle.
+  pub points_override: Option<NonZeroU16>,
+}
+
+impl Default for PaintArc {
+  fn default() -> Self {
+    Self {
+      color: color::WHITE,
+      radius: 1.,
+      inner_radius: 0.,
+      start_angle: 0.,
+      sweep_angle: TAU,
+      points_override: None,
+    }
+  }
+}
+
+impl PaintArc {
+  pub fn from_color(color: impl Into<Vec4>) -> Self {
+    Self {
+      color: color.into(),
+      ..Default::default()
+    }
+  }
+}
+
+impl PaintCommand for PaintArc {
+  fn paint(&self, _: &mut PainterInstance, into: &mut PaintBuffer) {
+    let sweep = self.sweep_angle.clamp(-TAU, TAU);
+    if sweep == 0. || self.radius <= 0. {
+      return
+    }
+
+    let radius = self.radius;
+    let inner_radius = self.inner_radius.clamp(0., radius);
+    let center = Vec2::splat(radius);
+
+    let point_count = self.points_override
+      .unwrap_or(point_count(radius, sweep))
+      .get()
+      .max(2) as u32;
+
+    // Get vertex at the given angle and radius
+    let point_impl = |angle: f32, radius: f32| Vertex {
+      position: center + vec2(angle.sin(), -angle.cos()) * radius,
+      uv: Vec2::ZERO,
+      color: self.color,
+    };
+
+    let idx_base = into.vertices.len() as u32;
+
+    if inner_radius == 0. {
+      // Pie: triangle fan around the center
+      into.vertices.reserve(point_count as usize + 1);
+      into.indices.reserve((point_count as usize - 1) * 3);
+      into.vertices.push(Vertex {
+        position: center,
+        uv: Vec2::ZERO,
+        color: self.color,
+      });
+      for i in 0..point_count {
+        let frac = i as f32 / (point_count - 1) as f32;
+        into.vertices.push(point_impl(self.start_angle + sweep * frac, radius));
+        if i > 0 {
+          into.indices.extend([
+            idx_base,
+            idx_base + i,
+            idx_base + i + 1,
+          ]);
+        }
+      }
+    } else {
+      // Ring segment: strip of quads between inner and outer radius
+      into.vertices.reserve(point_count as usize * 2);
+      into.indices.reserve((point_count as usize - 1) * 6);
+      for i in 0..point_count {
+        let frac = i as f32 / (point_count - 1) as f32;
+        let angle = self.start_angle + sweep * frac;
+        into.vertices.extend([
+          point_impl(angle, radius),
+          point_impl(angle, inner_radius),
+        ]);
+        if i > 0 {
+          let prev = idx_base + (i - 1) * 2;
+          let curr = idx_base + i * 2;
+          into.indices.extend([
+            prev, prev + 1, curr,
+            curr, prev + 1, curr + 1,
+          ]);
+        }
+      }
+    }
+  }
+
+  fn bounds(&self, _: &PainterInstance) -> Rect {
+    Rect {
+      position: Vec2::ZERO,
+      size: Vec2::splat(self.radius.max(0.) * 2.),
+    }
+  }
+
+  fn cache_hash(&self) -> u64 {
+    let mut hasher = rustc_hash::FxHasher::default();
+    hash_vec4(&mut hasher, self.color);
+    hash_vec2(&mut hasher, vec2(self.radius, self.inner_radius));
+    hash_vec2(&mut hasher, vec2(self.start_angle, self.sweep_angle));
+    hasher.write_u16(self.points_override.map(|x| x.get()).unwrap_or(0));
+    hasher.finish()
+  }
+}
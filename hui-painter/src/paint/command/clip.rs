@@ -0,0 +1,234 @@
+use core::hash::Hasher;
+use alloc::vec::Vec;
+use glam::Vec2;
+use hui_shared::rect::Rect;
+use crate::{
+  PainterInstance,
+  paint::{
+    buffer::{PaintBuffer, Vertex},
+    command::PaintCommand,
+  },
+  util::hash_vec2,
+};
+
+/// Maximum number of vertices a triangle can have after being clipped by 4 planes
+const MAX_CLIPPED_VERTICES: usize = 3 + 4;
+
+fn lerp_vertex(a: &Vertex, b: &Vertex, t: f32) -> Vertex {
+  Vertex {
+    position: a.position.lerp(b.position, t),
+    uv: a.uv.lerp(b.uv, t),
+    color: a.color.lerp(b.color, t),
+  }
+}
+
+/// Clip a convex polygon against a single axis-aligned plane (Sutherland-Hodgman)
+///
+/// Points for which `distance` is non-negative are considered inside
+fn clip_polygon(
+  input: &[Vertex],
+  output: &mut [Vertex; MAX_CLIPPED_VERTICES],
+  distance: impl Fn(Vec2) -> f32,
+) -> usize {
+  let mut count = 0;
+  for (idx, current) in input.iter().enumerate() {
+    let next = &input[(idx + 1) % input.len()];
+    let d_current = distance(current.position);
+    let d_next = distance(next.position);
+    if d_current >= 0. {
+      output[count] = *current;
+      count += 1;
+    }
+    if (d_current >= 0.) != (d_next >= 0.) {
+      output[count] = lerp_vertex(current, next, d_current / (d_current - d_next));
+      count += 1;
+    }
+  }
+  count
+}
+
+/// Clips the geometry of the child command to the given rectangle
+///
+/// Clipping is done on the CPU, by splitting the triangles crossing the edges of the rectangle\
+/// Texture coordinates and colors are interpolated for the newly created vertices
+pub struct PaintClip<T: PaintCommand + 'static> {
+  /// Clipping rectangle, in the same coordinate space as the child's vertices
+  pub rect: Rect,
+  pub child: T,
+}
+
+impl<T: PaintCommand + 'static> PaintCommand for PaintClip<T> {
+  fn pre_paint(&self, ctx: &mut PainterInstance) {
+    self.child.pre_paint(ctx);
+  }
+
+  fn paint(&self, ctx: &mut PainterInstance, into: &mut PaintBuffer) {
+    // remember the starting index
+    let starting_index = into.indices.len();
+
+    // paint children node
+    self.child.paint(ctx, into);
+
+    let min = self.rect.position;
+    let max = self.rect.position + self.rect.size;
+    let planes: [&dyn Fn(Vec2) -> f32; 4] = [
+      &|p| p.x - min.x,
+      &|p| max.x - p.x,
+      &|p| p.y - min.y,
+      &|p| max.y - p.y,
+    ];
+
+    // re-build the index buffer, leaving the unused vertices in place
+    let triangles: Vec<u32> = into.indices.drain(starting_index..).collect();
+    for triangle in triangles.chunks_exact(3) {
+      let vertices = [
+        into.vertices[triangle[0] as usize],
+        into.vertices[triangle[1] as usize],
+        into.vertices[triangle[2] as usize],
+      ];
+
+      // fast path: triangle is fully inside
+      if vertices.iter().all(|vtx| self.rect.contains_point(vtx.position)) {
+        into.indices.extend_from_slice(triangle);
+        continue
+      }
+
+      // fast path: triangle is fully outside of one of the planes
+      if planes.iter().any(|plane| vertices.iter().all(|vtx| plane(vtx.position) < 0.)) {
+        continue
+      }
+
+      // clip the triangle against each plane
+      let mut polygon = [vertices[0]; MAX_CLIPPED_VERTICES];
+      polygon[..3].copy_from_slice(&vertices);
+      let mut count = 3;
+      for plane in planes {
+        let mut clipped = [vertices[0]; MAX_CLIPPED_VERTICES];
+        count = clip_polygon(&polygon[..count], &mut clipped, plane);
+        polygon = clipped;
+        if count < 3 {
+          break
+        }
+      }
+      if count < 3 {
+        continue
+      }
+
+      // triangulate the resulting convex polygon as a fan
+      let idx_base = into.vertices.len() as u32;
+      into.vertices.extend_from_slice(&polygon[..count]);
+      for idx in 1..(count as u32 - 1) {
+        into.indices.extend([idx_base, idx_base + idx, idx_base + idx + 1]);
+      }
+    }
+  }
+
+  fn cache_hash(&self) -> u64 {
+    let mut hasher = rustc_hash::FxHasher::default();
+    hasher.write_u64(self.child.cache_hash());
+    hash_vec2(&mut hasher, self.rect.position);
+    hash_vec2(&mut hasher, self.rect.size);
+    hasher.finish()
+  }
+
+  fn bounds(&self, ctx: &PainterInstance) -> Rect {
+    let child = self.child.bounds(ctx);
+    let min = child.position.max(self.rect.position);
+    let max = (child.position + child.size).min(self.rect.position + self.rect.size);
+    Rect {
+      position: min,
+      size: (max - min).max(Vec2::ZERO),
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use glam::{vec2, Vec4};
+  use crate::paint::command::PaintRectangle;
+
+  fn vertex(position: Vec2, color: Vec4) -> Vertex {
+    Vertex { position, uv: Vec2::ZERO, color }
+  }
+
+  /// Paint the command and return the buffer
+  fn paint(command: &impl PaintCommand) -> PaintBuffer {
+    let mut ctx = PainterInstance::new();
+    let mut buffer = PaintBuffer::new();
+    command.paint(&mut ctx, &mut buffer);
+    buffer
+  }
+
+  /// Total area of all triangles in the buffer
+  fn area(buffer: &PaintBuffer) -> f32 {
+    buffer.indices.chunks_exact(3).map(|triangle| {
+      let [a, b, c] = [0, 1, 2].map(|idx| buffer.vertices[triangle[idx] as usize].position);
+      (b - a).perp_dot(c - a).abs() / 2.
+    }).sum()
+  }
+
+  #[test]
+  fn test_clip_polygon_half_plane() {
+    let triangle = [
+      vertex(vec2(0., 0.), Vec4::ZERO),
+      vertex(vec2(10., 0.), Vec4::ONE),
+      vertex(vec2(0., 10.), Vec4::ZERO),
+    ];
+    let mut output = [triangle[0]; MAX_CLIPPED_VERTICES];
+    let count = clip_polygon(&triangle, &mut output, |p| 5. - p.x);
+    assert_eq!(count, 4);
+    assert!(output[..count].iter().all(|vtx| vtx.position.x <= 5.));
+    //Color is interpolated for the new vertex halfway along the bottom edge
+    let new_vertex = output[..count].iter().find(|vtx| vtx.position == vec2(5., 0.)).unwrap();
+    assert_eq!(new_vertex.color, Vec4::splat(0.5));
+  }
+
+  #[test]
+  fn test_clip_polygon_fully_outside() {
+    let triangle = [
+      vertex(vec2(0., 0.), Vec4::ZERO),
+      vertex(vec2(10., 0.), Vec4::ZERO),
+      vertex(vec2(0., 10.), Vec4::ZERO),
+    ];
+    let mut output = [triangle[0]; MAX_CLIPPED_VERTICES];
+    assert_eq!(clip_polygon(&triangle, &mut output, |p| p.x - 20.), 0);
+  }
+
+  #[test]
+  fn test_paint_clip_inside() {
+    let buffer = paint(&PaintClip {
+      rect: Rect::new(vec2(-5., -5.), vec2(20., 20.)),
+      child: PaintRectangle { size: vec2(10., 10.), ..Default::default() },
+    });
+    assert_eq!(buffer.vertices.len(), 4);
+    assert_eq!(buffer.indices.len(), 6);
+  }
+
+  #[test]
+  fn test_paint_clip_outside() {
+    let buffer = paint(&PaintClip {
+      rect: Rect::new(vec2(20., 20.), vec2(10., 10.)),
+      child: PaintRectangle { size: vec2(10., 10.), ..Default::default() },
+    });
+    assert!(buffer.indices.is_empty());
+  }
+
+  #[test]
+  fn test_paint_clip_partial() {
+    let rect = Rect::new(vec2(5., 2.), vec2(10., 10.));
+    let buffer = paint(&PaintClip {
+      rect,
+      child: PaintRectangle { size: vec2(10., 10.), ..Default::default() },
+    });
+    assert!((area(&buffer) - 40.).abs() < 1e-4);
+    for &idx in &buffer.indices {
+      let position = buffer.vertices[idx as usize].position;
+      //Allow for rounding errors in the interpolated positions
+      assert!(
+        position.cmpge(rect.position - 1e-4).all() && position.cmple(rect.position + rect.size + 1e-4).all(),
+        "{position} is outside of the clip rect"
+      );
+    }
+  }
+}
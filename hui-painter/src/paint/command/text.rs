@@ -46,6 +46,39 @@ impl PaintText {
     vec![&font]
   }
 
+  /// Get the horizontal offsets of all caret positions within the text
+  ///
+  /// The returned vector contains one more element than there are characters in the text:\
+  /// element at index `i` is the offset of the caret placed right before the `i`-th character,\
+  /// and the last element is the offset of the caret placed after the last character
+  ///
+  /// Line breaks are not taken into account, the text is treated as a single line
+  pub fn caret_offsets(&self, ctx: &PainterInstance) -> Vec<f32> {
+    let font = ctx.fonts.get_fontdue_font(self.text.font)
+      .expect("FontHandle is invalid");
+    let mut offsets = Vec::with_capacity(self.text.text.len() + 1);
+    let mut position = 0.;
+    offsets.push(position);
+    for c in self.text.text.chars() {
+      // (must match the advance calculation used by fontdue's layout,
+      //  which advances by the rounded up advance width and doesn't apply kerning)
+      if !c.is_control() {
+        position += font.metrics(c, self.text.size).advance_width.ceil();
+      }
+      offsets.push(position);
+    }
+    offsets
+  }
+
+  /// Get the height of a single line of text
+  pub fn line_height(&self, ctx: &PainterInstance) -> f32 {
+    let font = ctx.fonts.get_fontdue_font(self.text.font)
+      .expect("FontHandle is invalid");
+    font.horizontal_line_metrics(self.text.size)
+      .map(|metrics| metrics.new_line_size.ceil())
+      .unwrap_or(self.text.size)
+  }
+
   fn build_layout(&self, font_array: &[&fontdue::Font]) -> Layout {
     let mut layout = Layout::new(CoordinateSystem::PositiveYDown);
     layout.append(
@@ -29,6 +29,43 @@ pub fn rgba_hex(value: u32) -> Vec4 {
   vec4(r as f32 / 255.0, g as f32 / 255.0, b as f32 / 255.0, a as f32 / 255.0)
 }
 
+/// Create a color from hue, saturation, value, alpha components (all in range `0.0..=1.0`)
+pub fn hsva(h: f32, s: f32, v: f32, a: f32) -> Vec4 {
+  let h = h.rem_euclid(1.0) * 6.0;
+  let c = v * s;
+  let x = c * (1.0 - (h % 2.0 - 1.0).abs());
+  let (r, g, b) = match h as u32 {
+    0 => (c, x, 0.0),
+    1 => (x, c, 0.0),
+    2 => (0.0, c, x),
+    3 => (0.0, x, c),
+    4 => (x, 0.0, c),
+    _ => (c, 0.0, x),
+  };
+  let m = v - c;
+  vec4(r + m, g + m, b + m, a)
+}
+
+/// Convert an RGBA color into hue, saturation, value, alpha components (all in range `0.0..=1.0`)
+///
+/// Hue is set to zero for shades of gray, and saturation is set to zero for black
+pub fn to_hsva(color: Vec4) -> Vec4 {
+  let max = color.x.max(color.y).max(color.z);
+  let min = color.x.min(color.y).min(color.z);
+  let delta = max - min;
+  let h = if delta == 0.0 {
+    0.0
+  } else if max == color.x {
+    ((color.y - color.z) / delta).rem_euclid(6.0)
+  } else if max == color.y {
+    (color.z - color.x) / delta + 2.0
+  } else {
+    (color.x - color.y) / delta + 4.0
+  };
+  let s = if max == 0.0 { 0.0 } else { delta / max };
+  vec4(h / 6.0, s, max, color.w)
+}
+
 #[cfg_attr(doc, doc="<span style='display: inline-block; background: repeating-conic-gradient(grey 0 25%,darkgrey 0 50%) 50%/8px 8px; width: 1em; height: 1em; border-radius: 50%; border: 1px solid black; vertical-align: -7%'></span>")]
 /// `#00000000` Transparent
 pub const TRANSPARENT: Vec4 = vec4(0.0, 0.0, 0.0, 0.0);
@@ -126,3 +163,48 @@ pub const OLIVE: Vec4 = vec4(0.5, 0.5, 0.0, 1.0);
 pub const SKY_BLUE: Vec4 = vec4(0.53, 0.81, 0.92, 1.0);
 
 //TODO color macro
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn assert_close(a: Vec4, b: Vec4) {
+    assert!(a.abs_diff_eq(b, 1e-5), "{a} != {b}");
+  }
+
+  #[test]
+  fn test_hsva_primary_colors() {
+    assert_close(hsva(0.0, 1.0, 1.0, 1.0), RED);
+    assert_close(hsva(1.0 / 3.0, 1.0, 1.0, 1.0), GREEN);
+    assert_close(hsva(2.0 / 3.0, 1.0, 1.0, 1.0), BLUE);
+    assert_close(hsva(1.0, 1.0, 1.0, 1.0), RED);
+    assert_close(hsva(0.5, 0.0, 1.0, 0.5), vec4(1.0, 1.0, 1.0, 0.5));
+    assert_close(hsva(0.5, 1.0, 0.0, 1.0), BLACK);
+  }
+
+  #[test]
+  fn test_to_hsva_gray_and_black() {
+    assert_close(to_hsva(vec4(0.5, 0.5, 0.5, 1.0)), vec4(0.0, 0.0, 0.5, 1.0));
+    assert_close(to_hsva(BLACK), vec4(0.0, 0.0, 0.0, 1.0));
+  }
+
+  #[test]
+  fn test_hsva_round_trip() {
+    for h in 0..12 {
+      for s in 1..=4 {
+        for v in 1..=4 {
+          let hsv = vec4(h as f32 / 12.0, s as f32 / 4.0, v as f32 / 4.0, 0.75);
+          assert_close(to_hsva(hsva(hsv.x, hsv.y, hsv.z, hsv.w)), hsv);
+        }
+      }
+    }
+  }
+
+  #[test]
+  fn test_rgba_round_trip() {
+    for color in [RED, BLUE, WHITE, BLACK, SKY_BLUE, vec4(0.2, 0.7, 0.4, 0.3)] {
+      let hsv = to_hsva(color);
+      assert_close(hsva(hsv.x, hsv.y, hsv.z, hsv.w), color);
+    }
+  }
+}
@@ -46,7 +46,6 @@ impl Rect {
     point.cmpge(self.position).all() && point.cmple(self.position + self.size).all()
   }
 
-  //TODO: return intersect rect
   /// Check if the rect intersects with another rect.
   pub fn intersects_rect(&self, other: Rect) -> bool {
     self.position.x < other.position.x + other.size.x
@@ -55,6 +54,18 @@ impl Rect {
       && self.position.y + self.size.y > other.position.y
   }
 
+  /// Get the intersection of two rects.
+  ///
+  /// Returns `None` if the rects do not intersect.
+  pub fn intersection(&self, other: Rect) -> Option<Rect> {
+    let min = self.position.max(other.position);
+    let max = (self.position + self.size).min(other.position + other.size);
+    (max.cmpgt(min).all()).then_some(Rect {
+      position: min,
+      size: max - min,
+    })
+  }
+
   /// Get width of the rectangle.
   ///
   /// To get both width and height, use the `size` property instead.
@@ -148,3 +159,40 @@ impl From<Rect> for [f32; 4] {
     [rect.position.x, rect.position.y, rect.size.x, rect.size.y]
   }
 }
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use glam::vec2;
+
+  #[test]
+  fn test_intersection_overlapping() {
+    let a = Rect::new(vec2(0., 0.), vec2(10., 10.));
+    let b = Rect::new(vec2(5., -5.), vec2(10., 10.));
+    assert_eq!(a.intersection(b), Some(Rect::new(vec2(5., 0.), vec2(5., 5.))));
+    assert_eq!(b.intersection(a), a.intersection(b));
+  }
+
+  #[test]
+  fn test_intersection_contained() {
+    let outer = Rect::new(vec2(0., 0.), vec2(10., 10.));
+    let inner = Rect::new(vec2(2., 3.), vec2(4., 5.));
+    assert_eq!(outer.intersection(inner), Some(inner));
+  }
+
+  #[test]
+  fn test_intersection_disjoint() {
+    let a = Rect::new(vec2(0., 0.), vec2(10., 10.));
+    let b = Rect::new(vec2(20., 0.), vec2(10., 10.));
+    assert_eq!(a.intersection(b), None);
+  }
+
+  #[test]
+  fn test_intersection_touching_edges() {
+    //Rects sharing only an edge do not intersect
+    let a = Rect::new(vec2(0., 0.), vec2(10., 10.));
+    let b = Rect::new(vec2(10., 0.), vec2(10., 10.));
+    assert_eq!(a.intersection(b), None);
+    assert!(!a.intersects_rect(b));
+  }
+}
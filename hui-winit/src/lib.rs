@@ -1,6 +1,126 @@
 use glam::vec2;
-use hui::{event::UiEvent, UiInstance};
-use winit::event::{Event, WindowEvent, MouseButton, ElementState};
+use hui::{event::UiEvent, input::KeyboardKey, UiInstance};
+use winit::{
+  event::{Event, WindowEvent, MouseButton, MouseScrollDelta, ElementState},
+  keyboard::{KeyCode, PhysicalKey},
+};
+
+fn translate_key_code(key: KeyCode) -> Option<KeyboardKey> {
+  Some(match key {
+    KeyCode::KeyA => KeyboardKey::A,
+    KeyCode::KeyB => KeyboardKey::B,
+    KeyCode::KeyC => KeyboardKey::C,
+    KeyCode::KeyD => KeyboardKey::D,
+    KeyCode::KeyE => KeyboardKey::E,
+    KeyCode::KeyF => KeyboardKey::F,
+    KeyCode::KeyG => KeyboardKey::G,
+    KeyCode::KeyH => KeyboardKey::H,
+    KeyCode::KeyI => KeyboardKey::I,
+    KeyCode::KeyJ => KeyboardKey::J,
+    KeyCode::KeyK => KeyboardKey::K,
+    KeyCode::KeyL => KeyboardKey::L,
+    KeyCode::KeyM => KeyboardKey::M,
+    KeyCode::KeyN => KeyboardKey::N,
+    KeyCode::KeyO => KeyboardKey::O,
+    KeyCode::KeyP => KeyboardKey::P,
+    KeyCode::KeyQ => KeyboardKey::Q,
+    KeyCode::KeyR => KeyboardKey::R,
+    KeyCode::KeyS => KeyboardKey::S,
+    KeyCode::KeyT => KeyboardKey::T,
+    KeyCode::KeyU => KeyboardKey::U,
+    KeyCode::KeyV => KeyboardKey::V,
+    KeyCode::KeyW => KeyboardKey::W,
+    KeyCode::KeyX => KeyboardKey::X,
+    KeyCode::KeyY => KeyboardKey::Y,
+    KeyCode::KeyZ => KeyboardKey::Z,
+    KeyCode::Digit0 => KeyboardKey::Num0,
+    KeyCode::Digit1 => KeyboardKey::Num1,
+    KeyCode::Digit2 => KeyboardKey::Num2,
+    KeyCode::Digit3 => KeyboardKey::Num3,
+    KeyCode::Digit4 => KeyboardKey::Num4,
+    KeyCode::Digit5 => KeyboardKey::Num5,
+    KeyCode::Digit6 => KeyboardKey::Num6,
+    KeyCode::Digit7 => KeyboardKey::Num7,
+    KeyCode::Digit8 => KeyboardKey::Num8,
+    KeyCode::Digit9 => KeyboardKey::Num9,
+    KeyCode::Numpad0 => KeyboardKey::Np0,
+    KeyCode::Numpad1 => KeyboardKey::Np1,
+    KeyCode::Numpad2 => KeyboardKey::Np2,
+    KeyCode::Numpad3 => KeyboardKey::Np3,
+    KeyCode::Numpad4 => KeyboardKey::Np4,
+    KeyCode::Numpad5 => KeyboardKey::Np5,
+    KeyCode::Numpad6 => KeyboardKey::Np6,
+    KeyCode::Numpad7 => KeyboardKey::Np7,
+    KeyCode::Numpad8 => KeyboardKey::Np8,
+    KeyCode::Numpad9 => KeyboardKey::Np9,
+    KeyCode::NumpadDivide => KeyboardKey::NpDivide,
+    KeyCode::NumpadMultiply => KeyboardKey::NpMultiply,
+    KeyCode::NumpadSubtract => KeyboardKey::NpSubtract,
+    KeyCode::NumpadAdd => KeyboardKey::NpAdd,
+    KeyCode::NumpadEnter => KeyboardKey::NpEnter,
+    KeyCode::NumpadDecimal => KeyboardKey::NpDecimal,
+    KeyCode::F1 => KeyboardKey::F1,
+    KeyCode::F2 => KeyboardKey::F2,
+    KeyCode::F3 => KeyboardKey::F3,
+    KeyCode::F4 => KeyboardKey::F4,
+    KeyCode::F5 => KeyboardKey::F5,
+    KeyCode::F6 => KeyboardKey::F6,
+    KeyCode::F7 => KeyboardKey::F7,
+    KeyCode::F8 => KeyboardKey::F8,
+    KeyCode::F9 => KeyboardKey::F9,
+    KeyCode::F10 => KeyboardKey::F10,
+    KeyCode::F11 => KeyboardKey::F11,
+    KeyCode::F12 => KeyboardKey::F12,
+    KeyCode::ArrowUp => KeyboardKey::Up,
+    KeyCode::ArrowDown => KeyboardKey::Down,
+    KeyCode::ArrowLeft => KeyboardKey::Left,
+    KeyCode::ArrowRight => KeyboardKey::Right,
+    KeyCode::Space => KeyboardKey::Space,
+    KeyCode::Enter => KeyboardKey::Enter,
+    KeyCode::Escape => KeyboardKey::Escape,
+    KeyCode::Backspace => KeyboardKey::Backspace,
+    KeyCode::Tab => KeyboardKey::Tab,
+    KeyCode::CapsLock => KeyboardKey::CapsLock,
+    KeyCode::ControlLeft => KeyboardKey::LControl,
+    KeyCode::ControlRight => KeyboardKey::RControl,
+    KeyCode::ShiftLeft => KeyboardKey::LShift,
+    KeyCode::ShiftRight => KeyboardKey::RShift,
+    KeyCode::AltLeft => KeyboardKey::LAlt,
+    KeyCode::AltRight => KeyboardKey::RAlt,
+    KeyCode::SuperLeft => KeyboardKey::LSuper,
+    KeyCode::SuperRight => KeyboardKey::RSuper,
+    KeyCode::Backquote => KeyboardKey::Grave,
+    KeyCode::Minus => KeyboardKey::Minus,
+    KeyCode::Equal => KeyboardKey::Equals,
+    KeyCode::BracketLeft => KeyboardKey::LeftBracket,
+    KeyCode::BracketRight => KeyboardKey::RightBracket,
+    KeyCode::Backslash => KeyboardKey::Backslash,
+    KeyCode::Semicolon => KeyboardKey::Semicolon,
+    KeyCode::Quote => KeyboardKey::Apostrophe,
+    KeyCode::Comma => KeyboardKey::Comma,
+    KeyCode::Period => KeyboardKey::Period,
+    KeyCode::Slash => KeyboardKey::Slash,
+    KeyCode::Insert => KeyboardKey::Insert,
+    KeyCode::Delete => KeyboardKey::Delete,
+    KeyCode::Home => KeyboardKey::Home,
+    KeyCode::End => KeyboardKey::End,
+    KeyCode::PageUp => KeyboardKey::PageUp,
+    KeyCode::PageDown => KeyboardKey::PageDown,
+    KeyCode::PrintScreen => KeyboardKey::PrintScreen,
+    KeyCode::ScrollLock => KeyboardKey::ScrollLock,
+    KeyCode::Pause => KeyboardKey::Pause,
+    KeyCode::ContextMenu => KeyboardKey::Menu,
+    KeyCode::NumLock => KeyboardKey::NumLock,
+    KeyCode::AudioVolumeMute => KeyboardKey::Mute,
+    KeyCode::AudioVolumeUp => KeyboardKey::VolumeUp,
+    KeyCode::AudioVolumeDown => KeyboardKey::VolumeDown,
+    KeyCode::MediaPlayPause => KeyboardKey::MediaPlay,
+    KeyCode::MediaStop => KeyboardKey::MediaStop,
+    KeyCode::MediaTrackNext => KeyboardKey::MediaNext,
+    KeyCode::MediaTrackPrevious => KeyboardKey::MediaPrevious,
+    _ => return None,
+  })
+}
 
 //TODO: check window id
 pub fn handle_winit_event<T>(ui: &mut UiInstance, event: &Event<T>) {
@@ -9,6 +129,14 @@ pub fn handle_winit_event<T>(ui: &mut UiInstance, event: &Event<T>) {
       WindowEvent::CursorMoved { position, .. } => {
         ui.push_event(UiEvent::MouseMove(vec2(position.x as f32, position.y as f32)));
       },
+      WindowEvent::MouseWheel { delta, .. } => {
+        //XXX: line height is not known here, assume 20px per line
+        const PIXELS_PER_LINE: f32 = 20.;
+        ui.push_event(UiEvent::MouseWheel(match delta {
+          MouseScrollDelta::LineDelta(x, y) => vec2(*x, *y) * PIXELS_PER_LINE,
+          MouseScrollDelta::PixelDelta(position) => vec2(position.x as f32, position.y as f32),
+        }));
+      },
       WindowEvent::MouseInput { state, button, .. } => {
         ui.push_event(UiEvent::MouseButton {
           button: match button {
@@ -24,7 +152,26 @@ pub fn handle_winit_event<T>(ui: &mut UiInstance, event: &Event<T>) {
           },
         })
       },
-      //TODO: translate keyboard input
+      WindowEvent::KeyboardInput { event, .. } => {
+        if let PhysicalKey::Code(code) = event.physical_key {
+          if let Some(key) = translate_key_code(code) {
+            ui.push_event(UiEvent::KeyboardButton {
+              key,
+              state: match event.state {
+                ElementState::Pressed => hui::input::ButtonState::Pressed,
+                ElementState::Released => hui::input::ButtonState::Released,
+              },
+            });
+          }
+        }
+        if event.state == ElementState::Pressed {
+          if let Some(text) = &event.text {
+            for c in text.chars() {
+              ui.push_event(UiEvent::TextInput(c));
+            }
+          }
+        }
+      },
       _ => (),
     }
   }
@@ -9,6 +9,7 @@ use crate::{
   rect::Rect,
   signal::SignalStore,
   state::StateRepo,
+  time::TimeInfo,
   UiInstance,
 };
 
@@ -29,12 +30,23 @@ pub struct MeasureContext<'a> {
 pub struct ProcessContext<'a> {
   pub painter: &'a mut PainterInstance,
   pub paint_target: &'a mut PaintList,
+  /// Paint target for things that must be drawn on top of everything else on the current layer (e.g. popups)\
+  /// Its contents are drawn right after the UI root being processed (below the layers above it), and are not affected by transformations applied to `paint_target`
+  pub paint_overlay: &'a mut PaintList,
   pub measure: &'a Response,
   pub layout: &'a LayoutInfo,
   pub state: &'a mut StateRepo,
   pub current_font: FontHandle,
   pub input: InputCtx<'a>,
   pub signal: &'a mut SignalStore,
+  /// Timing information of the current frame
+  pub time: TimeInfo,
+  /// Rect of the UI root currently being processed (as passed to [`UiInstance::add`])
+  pub root_rect: Rect,
+  /// Rects covered by the contents of `paint_overlay` that should block the pointer input (e.g. open popups)\
+  /// Starting from the next frame, pointer input inside of these rects is ignored by the elements of the current layer and the layers below it,
+  /// except for the ones using [`InputCtx::overlay`](crate::input::InputCtx::overlay)
+  pub overlay_occluders: &'a mut Vec<Rect>,
 }
 
 pub trait UiElement {
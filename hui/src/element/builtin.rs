@@ -3,6 +3,15 @@
 #[cfg(feature = "el_container")]
 pub mod container;
 
+#[cfg(feature = "el_overlay")]
+pub mod overlay;
+
+#[cfg(feature = "el_flex")]
+pub mod flex;
+
+#[cfg(feature = "el_grid")]
+pub mod grid;
+
 #[cfg(feature = "el_frame_view")]
 pub mod frame_view;
 
@@ -20,15 +29,69 @@ pub mod text;
 #[cfg(feature = "el_image")]
 pub mod image;
 
+#[cfg(feature = "el_text_input")]
+pub mod text_input;
+
+#[cfg(feature = "el_text_area")]
+pub mod text_area;
+
 // "Extras":
 // (meant to be replaced if needed)
 
 #[cfg(feature = "el_progress_bar")]
 pub mod progress_bar;
 
+#[cfg(feature = "el_radial_progress")]
+pub mod radial_progress;
+
 #[cfg(feature = "el_slider")]
 pub mod slider;
 
+#[cfg(feature = "el_color_picker")]
+pub mod color_picker;
+
+#[cfg(feature = "el_drag_value")]
+pub mod drag_value;
+
+#[cfg(feature = "el_button")]
+pub mod button;
+
+#[cfg(feature = "el_checkbox")]
+pub mod checkbox;
+
+#[cfg(feature = "el_toggle")]
+pub mod toggle;
+
+#[cfg(feature = "el_radio_group")]
+pub mod radio_group;
+
+#[cfg(feature = "el_dropdown")]
+pub mod dropdown;
+
+#[cfg(feature = "el_menu")]
+pub mod menu;
+
+#[cfg(feature = "el_collapsible")]
+pub mod collapsible;
+
+#[cfg(feature = "el_scroll_view")]
+pub mod scroll_view;
+
+#[cfg(feature = "el_split_pane")]
+pub mod split_pane;
+
+#[cfg(feature = "el_tabs")]
+pub mod tabs;
+
+#[cfg(feature = "el_tree_view")]
+pub mod tree_view;
+
+#[cfg(feature = "el_virtual_list")]
+pub mod virtual_list;
+
+#[cfg(any(feature = "el_scroll_view", feature = "el_virtual_list"))]
+pub(crate) mod scrollbar;
+
 // Wrappers:
 
 #[cfg(feature = "el_transformer")]
@@ -37,7 +100,5 @@ pub mod transformer;
 #[cfg(feature = "el_interactable")]
 pub mod interactable;
 
-//TODO add: Image
-//TODO add: OverlayContainer (for simply laying multiple elements on top of each other)
-//TODO add: Button, Checkbox, Dropdown, Input, Radio, Slider, Textarea, Toggle, etc.
-//TODO add: some sort of "flexible" container (like a poor man's flexbox)
+#[cfg(feature = "el_tooltip")]
+pub mod tooltip;
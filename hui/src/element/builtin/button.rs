@@ -0,0 +1,182 @@
+//! a clickable button element with separate frames for each interaction state
+
+use alloc::boxed::Box;
+use crate::{
+  element::{container::Container, ElementList, MeasureContext, ProcessContext, UiElement},
+  frame::{Frame, RectFrame},
+  layout::{Alignment2d, Direction, Size2d},
+  measure::Response,
+  rect::Sides,
+  signal::{trigger::SignalTrigger, Signal},
+};
+
+/// Interaction state of a [`Button`]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum ButtonInteraction {
+  /// Button is not being interacted with
+  #[default]
+  Idle,
+
+  /// Mouse pointer is over the button
+  Hovered,
+
+  /// Button is being held down
+  Pressed,
+
+  /// Button is disabled and does not react to any input
+  Disabled,
+}
+
+/// A clickable button element
+///
+/// Children are laid out the same way as in a [`Container`],\
+/// while the background frame depends on the current interaction state
+pub struct Button {
+  /// Inner container used to lay out the children elements
+  content: Container,
+
+  /// Frame used while the button is not being interacted with
+  pub frame: Box<dyn Frame>,
+
+  /// Frame used while the mouse pointer is over the button
+  pub frame_hovered: Box<dyn Frame>,
+
+  /// Frame used while the button is being held down
+  pub frame_pressed: Box<dyn Frame>,
+
+  /// Frame used while the button is disabled
+  pub frame_disabled: Box<dyn Frame>,
+
+  /// If set, the button does not react to any input and uses the disabled frame
+  pub disabled: bool,
+
+  /// Signal that will be fired when the button is clicked
+  pub on_click: Option<SignalTrigger>,
+}
+
+impl Default for Button {
+  fn default() -> Self {
+    Self {
+      content: Container::default()
+        .with_padding((10., 5.)),
+      frame: Box::new(RectFrame::color((0.25, 0.25, 0.25))),
+      frame_hovered: Box::new(RectFrame::color((0.35, 0.35, 0.35))),
+      frame_pressed: Box::new(RectFrame::color((0.15, 0.15, 0.15))),
+      frame_disabled: Box::new(RectFrame::color((0.2, 0.2, 0.2, 0.5))),
+      disabled: false,
+      on_click: None,
+    }
+  }
+}
+
+impl Button {
+  pub fn on_click<S: Signal, F: Fn() -> S + 'static>(self, f: F) -> Self {
+    Self {
+      on_click: Some(SignalTrigger::new(f)),
+      ..self
+    }
+  }
+
+  pub fn with_children(mut self, ui: impl FnOnce(&mut ElementList)) -> Self {
+    self.content = self.content.with_children(ui);
+    self
+  }
+
+  pub fn with_size(mut self, size: impl Into<Size2d>) -> Self {
+    self.content.size = size.into();
+    self
+  }
+
+  pub fn with_direction(mut self, direction: Direction) -> Self {
+    self.content.direction = direction;
+    self
+  }
+
+  pub fn with_gap(mut self, gap: f32) -> Self {
+    self.content.gap = gap;
+    self
+  }
+
+  pub fn with_padding(mut self, padding: impl Into<Sides<f32>>) -> Self {
+    self.content.padding = padding.into();
+    self
+  }
+
+  pub fn with_align(mut self, align: impl Into<Alignment2d>) -> Self {
+    self.content.align = align.into();
+    self
+  }
+
+  pub fn with_disabled(mut self, disabled: bool) -> Self {
+    self.disabled = disabled;
+    self
+  }
+
+  pub fn with_frame(mut self, frame: impl Frame + 'static) -> Self {
+    self.frame = Box::new(frame);
+    self
+  }
+
+  pub fn with_frame_hovered(mut self, frame: impl Frame + 'static) -> Self {
+    self.frame_hovered = Box::new(frame);
+    self
+  }
+
+  pub fn with_frame_pressed(mut self, frame: impl Frame + 'static) -> Self {
+    self.frame_pressed = Box::new(frame);
+    self
+  }
+
+  pub fn with_frame_disabled(mut self, frame: impl Frame + 'static) -> Self {
+    self.frame_disabled = Box::new(frame);
+    self
+  }
+
+  /// Get the frame that should be drawn for the given state
+  pub fn frame_for_state(&self, state: ButtonInteraction) -> &dyn Frame {
+    match state {
+      ButtonInteraction::Idle => &*self.frame,
+      ButtonInteraction::Hovered => &*self.frame_hovered,
+      ButtonInteraction::Pressed => &*self.frame_pressed,
+      ButtonInteraction::Disabled => &*self.frame_disabled,
+    }
+  }
+}
+
+impl UiElement for Button {
+  fn name(&self) -> &'static str {
+    "button"
+  }
+
+  fn size(&self) -> Option<Size2d> {
+    Some(self.content.size)
+  }
+
+  fn measure(&self, ctx: MeasureContext) -> Response {
+    self.content.measure(ctx)
+  }
+
+  fn process(&self, ctx: ProcessContext) {
+    let rect = ctx.measure.rect(ctx.layout.position);
+
+    let state = if self.disabled {
+      ButtonInteraction::Disabled
+    } else if !ctx.input.check_hover(rect) {
+      ButtonInteraction::Idle
+    } else if ctx.input.check_active(rect).is_some() {
+      ButtonInteraction::Pressed
+    } else {
+      ButtonInteraction::Hovered
+    };
+
+    self.frame_for_state(state).draw(ctx.paint_target, rect);
+
+    if !self.disabled && ctx.input.check_click(rect).is_some() {
+      if let Some(signal) = &self.on_click {
+        signal.fire(ctx.signal);
+      }
+    }
+
+    self.content.process(ctx);
+  }
+}
@@ -0,0 +1,113 @@
+//! a checkbox element that allows toggling a boolean value
+
+use alloc::boxed::Box;
+use derive_setters::Setters;
+use crate::{
+  element::{MeasureContext, ProcessContext, UiElement},
+  frame::{stack::FrameStackExt, Frame, RectFrame},
+  layout::{compute_size, Size, Size2d},
+  measure::Response,
+  signal::{trigger::SignalTriggerArg, Signal},
+};
+
+/// A checkbox element that allows toggling a boolean value
+///
+/// Clicking the checkbox fires the `on_change` signal with the new (inverted) value
+#[derive(Setters)]
+#[setters(no_std, prefix = "with_")]
+pub struct Checkbox {
+  /// Current value of the checkbox
+  pub checked: bool,
+
+  /// Size of the element
+  #[setters(into)]
+  pub size: Size2d,
+
+  /// Frame used while the checkbox is not checked
+  #[setters(skip)]
+  pub frame_unchecked: Box<dyn Frame>,
+
+  /// Frame used while the checkbox is checked
+  #[setters(skip)]
+  pub frame_checked: Box<dyn Frame>,
+
+  /// Signal that will be fired with the new value when the checkbox is clicked
+  #[setters(skip)]
+  pub on_change: Option<SignalTriggerArg<bool>>,
+}
+
+impl Default for Checkbox {
+  fn default() -> Self {
+    Self {
+      checked: false,
+      size: (Size::Auto, Size::Auto).into(),
+      frame_unchecked: Box::new(RectFrame::color((0.25, 0.25, 0.25))),
+      frame_checked: Box::new(
+        RectFrame::color((0.25, 0.25, 0.25))
+          .stack(RectFrame::color((0.0, 0.0, 1.0)).with_inset(4.))
+      ),
+      on_change: None,
+    }
+  }
+}
+
+impl Checkbox {
+  pub const DEFAULT_SIZE: f32 = 20.0;
+
+  pub fn new(checked: bool) -> Self {
+    Self {
+      checked,
+      ..Default::default()
+    }
+  }
+
+  pub fn on_change<S: Signal, T: Fn(bool) -> S + 'static>(self, f: T) -> Self {
+    Self {
+      on_change: Some(SignalTriggerArg::new(f)),
+      ..self
+    }
+  }
+
+  pub fn with_frame_unchecked(mut self, frame: impl Frame + 'static) -> Self {
+    self.frame_unchecked = Box::new(frame);
+    self
+  }
+
+  pub fn with_frame_checked(mut self, frame: impl Frame + 'static) -> Self {
+    self.frame_checked = Box::new(frame);
+    self
+  }
+}
+
+impl UiElement for Checkbox {
+  fn name(&self) -> &'static str {
+    "checkbox"
+  }
+
+  fn size(&self) -> Option<Size2d> {
+    Some(self.size)
+  }
+
+  fn measure(&self, ctx: MeasureContext) -> Response {
+    Response {
+      size: compute_size(ctx.layout, self.size, (Self::DEFAULT_SIZE, Self::DEFAULT_SIZE).into()),
+      ..Default::default()
+    }
+  }
+
+  fn process(&self, ctx: ProcessContext) {
+    let rect = ctx.measure.rect(ctx.layout.position);
+
+    if self.checked {
+      self.frame_checked.draw(ctx.paint_target, rect);
+    } else {
+      self.frame_unchecked.draw(ctx.paint_target, rect);
+    }
+
+    if ctx.input.check_click(rect).is_some() {
+      if let Some(signal) = &self.on_change {
+        signal.fire(ctx.signal, !self.checked);
+      }
+    }
+  }
+}
@@ -0,0 +1,471 @@
+//! collapsible section element with a clickable header, and an accordion grouping them
+
+use core::hash::Hash;
+use alloc::{boxed::Box, vec::Vec};
+use derive_setters::Setters;
+use glam::{vec2, Vec2};
+use hui_painter::paint::command::{PaintClip, PaintList};
+use crate::{
+  element::{container::Container, ElementList, MeasureContext, ProcessContext, UiElement},
+  frame::{Frame, RectFrame},
+  layout::{compute_size, Direction, LayoutInfo, Size, Size2d},
+  measure::Response,
+  rect::{Rect, Sides},
+  signal::{trigger::SignalTriggerArg, Signal},
+  state::{state_id, State, StateId, StateRepo},
+};
+
+/// State of a [`Collapsible`] element
+#[derive(Clone, Copy, Debug, Default)]
+pub struct CollapsibleState {
+  /// Whether the section is open
+  pub open: bool,
+
+  /// How much of the body is currently visible, in range `0.0..=1.0`\
+  /// Only differs from `open` while the height is being animated
+  pub progress: f32,
+}
+
+impl State for CollapsibleState {}
+
+/// Callback building the contents of the body of a collapsible section
+pub type CollapsibleBodyFn = dyn Fn(&mut ElementList);
+
+struct CollapsibleUserData {
+  header: Response,
+  header_height: f32,
+  body: Option<(Container, Response)>,
+}
+
+/// A section that can be opened or closed by clicking its header
+///
+/// The body is built by the `body` callback, which is only called while the section is (at least partially) open\
+/// The open state is stored in the [`StateRepo`](crate::state::StateRepo) under the given id
+///
+/// If `animation_duration` is set, the height of the body is animated when opening or closing the section\
+/// (this requires the UI clock to be updated, see [`UiInstance::advance_time`](crate::UiInstance::advance_time))
+#[derive(Setters)]
+#[setters(no_std, prefix = "with_")]
+pub struct Collapsible {
+  /// Id used to store the state of the element
+  #[setters(skip)]
+  pub id: StateId,
+
+  /// Element displayed in the header
+  #[setters(skip)]
+  pub header: Box<dyn UiElement>,
+
+  /// Callback that builds the contents of the body
+  #[setters(skip)]
+  pub body: Box<CollapsibleBodyFn>,
+
+  /// Size of the element
+  #[setters(into)]
+  pub size: Size2d,
+
+  /// Whether the section should be open by default
+  pub default_open: bool,
+
+  /// Duration of the open/close animation, in seconds\
+  /// If set to `None`, the section opens and closes instantly
+  #[setters(into)]
+  pub animation_duration: Option<f32>,
+
+  /// Padding inside the header
+  #[setters(into)]
+  pub header_padding: Sides<f32>,
+
+  /// Padding inside the body
+  #[setters(into)]
+  pub body_padding: Sides<f32>,
+
+  /// Gap between the elements of the body
+  pub body_gap: f32,
+
+  /// Header frame
+  #[setters(skip)]
+  pub header_frame: Box<dyn Frame>,
+
+  /// Header frame used while the mouse pointer is over it
+  #[setters(skip)]
+  pub header_frame_hovered: Box<dyn Frame>,
+
+  /// Body frame
+  #[setters(skip)]
+  pub body_frame: Box<dyn Frame>,
+
+  /// Signal that will be fired with the new open state when the header gets clicked\
+  /// (or when the section gets closed by the [`Accordion`] it belongs to)
+  #[setters(skip)]
+  pub on_toggle: Option<SignalTriggerArg<bool>>,
+}
+
+impl Collapsible {
+  pub fn new(id: impl Hash, header: impl UiElement + 'static) -> Self {
+    Self {
+      id: state_id(id),
+      header: Box::new(header),
+      body: Box::new(|_| ()),
+      size: (Size::Relative(1.), Size::Auto).into(),
+      default_open: false,
+      animation_duration: None,
+      header_padding: Sides::horizontal_vertical(5., 3.),
+      body_padding: Sides::all(5.),
+      body_gap: 0.,
+      header_frame: Box::new(RectFrame::color((0.2, 0.2, 0.2))),
+      header_frame_hovered: Box::new(RectFrame::color((0.25, 0.25, 0.25))),
+      body_frame: Box::new(RectFrame::color((0.15, 0.15, 0.15))),
+      on_toggle: None,
+    }
+  }
+
+  /// Set the callback that builds the contents of the body
+  pub fn with_body(mut self, body: impl Fn(&mut ElementList) + 'static) -> Self {
+    self.body = Box::new(body);
+    self
+  }
+
+  pub fn on_toggle<S: Signal, T: Fn(bool) -> S + 'static>(self, f: T) -> Self {
+    Self {
+      on_toggle: Some(SignalTriggerArg::new(f)),
+      ..self
+    }
+  }
+
+  pub fn with_header_frame(mut self, frame: impl Frame + 'static) -> Self {
+    self.header_frame = Box::new(frame);
+    self
+  }
+
+  pub fn with_header_frame_hovered(mut self, frame: impl Frame + 'static) -> Self {
+    self.header_frame_hovered = Box::new(frame);
+    self
+  }
+
+  pub fn with_body_frame(mut self, frame: impl Frame + 'static) -> Self {
+    self.body_frame = Box::new(frame);
+    self
+  }
+
+  fn default_state(&self) -> CollapsibleState {
+    CollapsibleState {
+      open: self.default_open,
+      progress: if self.default_open { 1. } else { 0. },
+    }
+  }
+}
+
+impl UiElement for Collapsible {
+  fn name(&self) -> &'static str {
+    "collapsible"
+  }
+
+  fn size(&self) -> Option<Size2d> {
+    Some(self.size)
+  }
+
+  fn measure(&self, ctx: MeasureContext) -> Response {
+    let max_size = compute_size(ctx.layout, self.size, ctx.layout.max_size);
+    let state = ctx.state.peek::<CollapsibleState>(self.id).copied()
+      .unwrap_or_else(|| self.default_state());
+
+    //Header
+    let header = self.header.measure(MeasureContext {
+      painter: ctx.painter,
+      layout: &LayoutInfo {
+        position: ctx.layout.position + vec2(self.header_padding.left, self.header_padding.top),
+        max_size: max_size - vec2(self.header_padding.sum_horizontal(), self.header_padding.sum_vertical()),
+        direction: Direction::Vertical,
+        remaining_space: None,
+      },
+      state: ctx.state,
+      current_font: ctx.current_font,
+    });
+    let header_height = header.size.y + self.header_padding.sum_vertical();
+    let mut width = header.size.x + self.header_padding.sum_horizontal();
+    let mut height = header_height;
+
+    //Body is only built if it's at least partially visible
+    let body = (state.progress > 0.).then(|| {
+      let container = Container::default()
+        .with_size((Size::Relative(1.), Size::Auto))
+        .with_padding(self.body_padding)
+        .with_gap(self.body_gap)
+        .with_children(|ui| (self.body)(ui));
+      let measure = container.measure(MeasureContext {
+        painter: ctx.painter,
+        layout: &LayoutInfo {
+          position: ctx.layout.position + vec2(0., header_height),
+          max_size: vec2(max_size.x, (ctx.layout.max_size.y - header_height).max(0.)),
+          direction: Direction::Vertical,
+          remaining_space: None,
+        },
+        state: ctx.state,
+        current_font: ctx.current_font,
+      });
+      width = width.max(measure.size.x);
+      height += measure.size.y * state.progress;
+      (container, measure)
+    });
+
+    Response {
+      size: compute_size(ctx.layout, self.size, vec2(width, height)),
+      user_data: Some(Box::new(CollapsibleUserData {
+        header,
+        header_height,
+        body,
+      })),
+      ..Default::default()
+    }
+  }
+
+  fn process(&self, ctx: ProcessContext) {
+    let user_data: &CollapsibleUserData = ctx.measure.user_data
+      .as_ref().expect("no user data attached to collapsible")
+      .downcast_ref().expect("invalid user data type");
+
+    let rect = ctx.measure.rect(ctx.layout.position);
+    let header_rect = Rect::new(rect.position, vec2(rect.size.x, user_data.header_height));
+
+    //Toggle the section on header click
+    let default_state = self.default_state();
+    let state = ctx.state.acquire_mut_or_insert(self.id, default_state);
+    if ctx.input.check_click(header_rect).is_some() {
+      state.open = !state.open;
+      if let Some(signal) = &self.on_toggle {
+        signal.fire(ctx.signal, state.open);
+      }
+    }
+
+    //Animate the body height
+    let target = if state.open { 1. } else { 0. };
+    state.progress = match self.animation_duration {
+      Some(duration) if duration > 0. => {
+        let step = (ctx.time.delta / duration).max(0.);
+        state.progress + (target - state.progress).clamp(-step, step)
+      },
+      _ => target,
+    };
+    if (target - state.progress).abs() < f32::EPSILON {
+      state.progress = target;
+    }
+
+    //Draw the header
+    if ctx.input.check_hover(header_rect) {
+      self.header_frame_hovered.draw(ctx.paint_target, header_rect);
+    } else {
+      self.header_frame.draw(ctx.paint_target, header_rect);
+    }
+    self.header.process(ProcessContext {
+      painter: ctx.painter,
+      measure: &user_data.header,
+      layout: &LayoutInfo {
+        position: header_rect.position + vec2(self.header_padding.left, self.header_padding.top),
+        max_size: header_rect.size - vec2(self.header_padding.sum_horizontal(), self.header_padding.sum_vertical()),
+        direction: Direction::Vertical,
+        remaining_space: None,
+      },
+      paint_target: ctx.paint_target,
+      paint_overlay: ctx.paint_overlay,
+      state: ctx.state,
+      current_font: ctx.current_font,
+      input: ctx.input,
+      signal: ctx.signal,
+      time: ctx.time,
+      root_rect: ctx.root_rect,
+      overlay_occluders: ctx.overlay_occluders,
+    });
+
+    //Draw the body, clipping it while it's partially hidden
+    let Some((container, measure)) = &user_data.body else {
+      return
+    };
+    let body_rect = Rect::new(
+      rect.position + vec2(0., user_data.header_height),
+      vec2(rect.size.x, rect.size.y - user_data.header_height),
+    );
+    if body_rect.size.y <= 0. {
+      return
+    }
+    let mut sub_list = PaintList::new_empty();
+    self.body_frame.draw(&mut sub_list, Rect::new(body_rect.position, vec2(body_rect.size.x, measure.size.y)));
+    container.process(ProcessContext {
+      painter: ctx.painter,
+      measure,
+      layout: &LayoutInfo {
+        position: body_rect.position,
+        max_size: vec2(body_rect.size.x, measure.size.y),
+        direction: Direction::Vertical,
+        remaining_space: None,
+      },
+      paint_target: &mut sub_list,
+      paint_overlay: ctx.paint_overlay,
+      state: ctx.state,
+      current_font: ctx.current_font,
+      input: ctx.input.with_clip(body_rect),
+      signal: ctx.signal,
+      time: ctx.time,
+      root_rect: ctx.root_rect,
+      overlay_occluders: ctx.overlay_occluders,
+    });
+    if body_rect.size.y < measure.size.y {
+      ctx.paint_target.add(PaintClip {
+        rect: body_rect,
+        child: sub_list,
+      });
+    } else {
+      ctx.paint_target.add(sub_list);
+    }
+  }
+}
+
+/// A vertical group of [`Collapsible`] sections, only allowing one of them to be open at a time
+///
+/// Opening a section closes the one that was previously open\
+/// (firing its `on_toggle` signal, if any)
+#[derive(Setters)]
+#[setters(no_std, prefix = "with_")]
+pub struct Accordion {
+  /// Sections of the accordion
+  #[setters(skip)]
+  pub sections: Vec<Collapsible>,
+
+  /// Size of the element
+  #[setters(into)]
+  pub size: Size2d,
+
+  /// Gap between the sections
+  pub gap: f32,
+
+  /// Signal that will be fired with the index of the open section (if any) when it changes
+  #[setters(skip)]
+  pub on_change: Option<SignalTriggerArg<Option<usize>>>,
+}
+
+impl Default for Accordion {
+  fn default() -> Self {
+    Self {
+      sections: Vec::new(),
+      size: (Size::Relative(1.), Size::Auto).into(),
+      gap: 0.,
+      on_change: None,
+    }
+  }
+}
+
+impl Accordion {
+  /// Add a section to the accordion
+  pub fn with_section(mut self, section: Collapsible) -> Self {
+    self.sections.push(section);
+    self
+  }
+
+  /// Add multiple sections to the accordion
+  pub fn with_sections(mut self, sections: impl IntoIterator<Item = Collapsible>) -> Self {
+    self.sections.extend(sections);
+    self
+  }
+
+  pub fn on_change<S: Signal, T: Fn(Option<usize>) -> S + 'static>(self, f: T) -> Self {
+    Self {
+      on_change: Some(SignalTriggerArg::new(f)),
+      ..self
+    }
+  }
+}
+
+impl UiElement for Accordion {
+  fn name(&self) -> &'static str {
+    "accordion"
+  }
+
+  fn size(&self) -> Option<Size2d> {
+    Some(self.size)
+  }
+
+  fn measure(&self, ctx: MeasureContext) -> Response {
+    let max_size = compute_size(ctx.layout, self.size, ctx.layout.max_size);
+    let mut size = Vec2::ZERO;
+    let measures: Vec<Response> = self.sections.iter().enumerate().map(|(index, section)| {
+      if index > 0 {
+        size.y += self.gap;
+      }
+      let measure = section.measure(MeasureContext {
+        painter: ctx.painter,
+        layout: &LayoutInfo {
+          position: ctx.layout.position + vec2(0., size.y),
+          max_size: vec2(max_size.x, (max_size.y - size.y).max(0.)),
+          direction: Direction::Vertical,
+          remaining_space: None,
+        },
+        state: ctx.state,
+        current_font: ctx.current_font,
+      });
+      size.x = size.x.max(measure.size.x);
+      size.y += measure.size.y;
+      measure
+    }).collect();
+    Response {
+      size: compute_size(ctx.layout, self.size, size),
+      user_data: Some(Box::new(measures)),
+      ..Default::default()
+    }
+  }
+
+  fn process(&self, ctx: ProcessContext) {
+    let measures: &Vec<Response> = ctx.measure.user_data
+      .as_ref().expect("no user data attached to accordion")
+      .downcast_ref().expect("invalid user data type");
+
+    let is_open = |state: &StateRepo, section: &Collapsible| {
+      state.peek::<CollapsibleState>(section.id).map_or(section.default_open, |state| state.open)
+    };
+    let was_open: Vec<bool> = self.sections.iter().map(|section| is_open(ctx.state, section)).collect();
+
+    let mut y = 0.;
+    for (section, measure) in self.sections.iter().zip(measures) {
+      section.process(ProcessContext {
+        painter: ctx.painter,
+        measure,
+        layout: &LayoutInfo {
+          position: ctx.layout.position + vec2(0., y),
+          max_size: vec2(ctx.measure.size.x, measure.size.y),
+          direction: Direction::Vertical,
+          remaining_space: None,
+        },
+        paint_target: ctx.paint_target,
+        paint_overlay: ctx.paint_overlay,
+        state: ctx.state,
+        current_font: ctx.current_font,
+        input: ctx.input,
+        signal: ctx.signal,
+        time: ctx.time,
+        root_rect: ctx.root_rect,
+        overlay_occluders: ctx.overlay_occluders,
+      });
+      y += measure.size.y + self.gap;
+    }
+
+    //Keep only one section open, preferring the one opened during this frame
+    let now_open: Vec<bool> = self.sections.iter().map(|section| is_open(ctx.state, section)).collect();
+    let open = (0..self.sections.len())
+      .find(|&index| now_open[index] && !was_open[index])
+      .or_else(|| now_open.iter().position(|&open| open));
+    for (index, section) in self.sections.iter().enumerate() {
+      if Some(index) != open {
+        if let Some(state) = ctx.state.acquire_mut::<CollapsibleState>(section.id).filter(|state| state.open) {
+          state.open = false;
+          if let Some(signal) = &section.on_toggle {
+            signal.fire(ctx.signal, false);
+          }
+        }
+      }
+    }
+
+    if was_open.iter().position(|&open| open) != open {
+      if let Some(signal) = &self.on_change {
+        signal.fire(ctx.signal, open);
+      }
+    }
+  }
+}
@@ -0,0 +1,512 @@
+//! a color picker element with a saturation/value square, hue strip and an alpha bar
+
+use core::hash::Hash;
+use alloc::{borrow::Cow, boxed::Box, string::String};
+use derive_setters::Setters;
+use glam::{vec2, vec3, vec4, Affine2, Vec2, Vec3, Vec4};
+use hui_painter::{
+  paint::command::{text::PaintText, PaintList, PaintTransform},
+  text::FontHandle,
+  PainterInstance,
+};
+use crate::{
+  color,
+  element::{text_input::{closest_caret, TextInputState}, MeasureContext, ProcessContext, UiElement},
+  event::UiEvent,
+  frame::{stack::FrameStackExt, Frame, RectFrame},
+  input::{ButtonState, KeyboardKey, MouseButton},
+  layout::{compute_size, Size, Size2d},
+  measure::Response,
+  rect::{Corners, FillColor, Rect, Sides},
+  signal::{trigger::SignalTriggerArg, Signal},
+  state::{state_id, State, StateId},
+};
+
+/// Format the color as a `#RRGGBB` or `#RRGGBBAA` hex string
+fn format_hex(color: Vec4, alpha: bool) -> String {
+  let [r, g, b, a] = (color.clamp(Vec4::ZERO, Vec4::ONE) * 255.).round().to_array().map(|x| x as u8);
+  match alpha {
+    true => format!("#{r:02X}{g:02X}{b:02X}{a:02X}"),
+    false => format!("#{r:02X}{g:02X}{b:02X}"),
+  }
+}
+
+/// Parse a `#RRGGBB` or `#RRGGBBAA` hex string (the `#` is optional)
+fn parse_hex(text: &str) -> Option<Vec4> {
+  let hex = text.trim().trim_start_matches('#');
+  if !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+    return None
+  }
+  let value = u32::from_str_radix(hex, 16).ok()?;
+  match hex.len() {
+    6 => Some(color::rgb_hex(value)),
+    8 => Some(color::rgba_hex(value.rotate_right(8))),
+    _ => None,
+  }
+}
+
+/// State of a [`ColorPicker`] element
+#[derive(Default)]
+pub struct ColorPickerState {
+  /// Currently selected hue, saturation and value
+  ///
+  /// Stored separately from the color, as hue and saturation can't be recovered from RGB for some colors
+  /// (e.g. hue for shades of gray)
+  pub hsv: Vec3,
+
+  /// Color the HSV values were last synchronized with
+  pub synced: Option<Vec4>,
+
+  /// Editing state of the hex input
+  pub hex: TextInputState,
+}
+
+impl State for ColorPickerState {}
+
+impl ColorPickerState {
+  /// Update the HSV values to match the color, keeping the hue and saturation if they can't be recovered
+  fn sync(&mut self, color: Vec4) {
+    if self.synced == Some(color) {
+      return
+    }
+    let hsva = color::to_hsva(color);
+    self.hsv.z = hsva.z;
+    if hsva.z > 0. {
+      self.hsv.y = hsva.y;
+      if hsva.y > 0. {
+        self.hsv.x = hsva.x;
+      }
+    }
+    self.synced = Some(color);
+  }
+}
+
+/// Rects of all parts of a [`ColorPicker`]
+struct ColorPickerLayout {
+  square: Rect,
+  hue: Rect,
+  alpha: Option<Rect>,
+  hex: Option<Rect>,
+}
+
+/// A color picker element
+///
+/// Consists of a saturation/value square, a hue strip, an optional alpha bar (drawn on top of a checkerboard)
+/// and an optional hex input field\
+/// The selected color is set by the [`ColorPicker::value`], so you should update it in response to the `on_change` signal
+#[derive(Setters)]
+#[setters(no_std, prefix = "with_")]
+pub struct ColorPicker {
+  /// Id used to store the state of the element
+  #[setters(skip)]
+  pub id: StateId,
+
+  /// Currently selected color (RGBA)
+  #[setters(into)]
+  pub value: Vec4,
+
+  /// Size of the element
+  ///
+  /// If the height is set to `Size::Auto`, the saturation/value square will be as tall as it is wide
+  #[setters(into)]
+  pub size: Size2d,
+
+  /// Whether to show the alpha bar (if disabled, the alpha of the value is preserved)
+  pub alpha: bool,
+
+  /// Whether to show the hex input field
+  pub hex_input: bool,
+
+  /// Gap between the parts of the picker, in pixels
+  pub gap: f32,
+
+  /// Height of the hue strip and the alpha bar, in pixels
+  pub bar_height: f32,
+
+  /// Size of the checkerboard squares drawn behind the alpha bar, in pixels
+  ///
+  /// Set to `0.0` to disable the checkerboard
+  pub checker_size: f32,
+
+  /// Colors of the checkerboard squares
+  pub checker_colors: (Vec4, Vec4),
+
+  /// Size of the handle in the saturation/value square, in pixels
+  pub handle_size: f32,
+
+  /// Width of the markers on the hue strip and the alpha bar, in pixels
+  pub marker_width: f32,
+
+  /// Padding inside the hex input field
+  #[setters(into)]
+  pub hex_padding: Sides<f32>,
+
+  /// Color of the hex input text
+  #[setters(into)]
+  pub text_color: Vec4,
+
+  /// Font to use for rendering the hex input text\
+  /// If set to `None` either currently selected font or the default font will be used
+  #[setters(into)]
+  pub font: Option<FontHandle>,
+
+  /// Size of the hex input text, in points (these are not pixels)
+  pub text_size: f32,
+
+  /// Frame of the handle in the saturation/value square
+  #[setters(skip)]
+  pub handle: Box<dyn Frame>,
+
+  /// Frame of the markers on the hue strip and the alpha bar
+  #[setters(skip)]
+  pub marker: Box<dyn Frame>,
+
+  /// Background frame of the hex input field
+  #[setters(skip)]
+  pub hex_background: Box<dyn Frame>,
+
+  /// Background frame of the hex input field while focused
+  #[setters(skip)]
+  pub hex_background_focused: Box<dyn Frame>,
+
+  /// Caret frame of the hex input field
+  #[setters(skip)]
+  pub caret: Box<dyn Frame>,
+
+  /// Signal that will be fired with the new color (RGBA) whenever it changes
+  #[setters(skip)]
+  pub on_change: Option<SignalTriggerArg<Vec4>>,
+}
+
+impl ColorPicker {
+  pub const DEFAULT_WIDTH: f32 = 200.;
+
+  pub fn new(id: impl Hash) -> Self {
+    Self {
+      id: state_id(id),
+      value: color::WHITE,
+      size: (Size::Absolute(Self::DEFAULT_WIDTH), Size::Auto).into(),
+      alpha: true,
+      hex_input: false,
+      gap: 6.,
+      bar_height: 14.,
+      checker_size: 7.,
+      checker_colors: (vec4(0.8, 0.8, 0.8, 1.), vec4(0.6, 0.6, 0.6, 1.)),
+      handle_size: 12.,
+      marker_width: 6.,
+      hex_padding: Sides::horizontal_vertical(5., 3.),
+      text_color: Vec4::new(1., 1., 1., 1.),
+      font: None,
+      text_size: 16.,
+      handle: Box::new(
+        RectFrame::color(color::BLACK).with_corner_radius(6.)
+          .stack(RectFrame::color(color::WHITE).with_corner_radius(4.5).with_inset(1.5))
+      ),
+      marker: Box::new(
+        RectFrame::color(color::BLACK).with_corner_radius(2.)
+          .stack(RectFrame::color(color::WHITE).with_corner_radius(1.).with_inset(1.5))
+      ),
+      hex_background: Box::new(RectFrame::color((0.1, 0.1, 0.1))),
+      hex_background_focused: Box::new(RectFrame::color((0.15, 0.15, 0.15))),
+      caret: Box::new(RectFrame::color((1., 1., 1.))),
+      on_change: None,
+    }
+  }
+
+  pub fn on_change<S: Signal, T: Fn(Vec4) -> S + 'static>(self, f: T) -> Self {
+    Self {
+      on_change: Some(SignalTriggerArg::new(f)),
+      ..self
+    }
+  }
+
+  pub fn with_handle(mut self, frame: impl Frame + 'static) -> Self {
+    self.handle = Box::new(frame);
+    self
+  }
+
+  pub fn with_marker(mut self, frame: impl Frame + 'static) -> Self {
+    self.marker = Box::new(frame);
+    self
+  }
+
+  pub fn with_hex_background(mut self, frame: impl Frame + 'static) -> Self {
+    self.hex_background = Box::new(frame);
+    self
+  }
+
+  pub fn with_hex_background_focused(mut self, frame: impl Frame + 'static) -> Self {
+    self.hex_background_focused = Box::new(frame);
+    self
+  }
+
+  pub fn with_caret(mut self, frame: impl Frame + 'static) -> Self {
+    self.caret = Box::new(frame);
+    self
+  }
+
+  fn paint_text(&self, text: impl Into<Cow<'static, str>>, current_font: FontHandle) -> PaintText {
+    PaintText::new(text, self.font.unwrap_or(current_font), self.text_size, self.text_color)
+  }
+
+  fn hex_height(&self, painter: &PainterInstance, current_font: FontHandle) -> f32 {
+    self.paint_text("", current_font).line_height(painter) + self.hex_padding.sum_vertical()
+  }
+
+  /// Total height of everything below the saturation/value square
+  fn bars_height(&self, hex_height: f32) -> f32 {
+    let mut height = self.gap + self.bar_height;
+    if self.alpha {
+      height += self.gap + self.bar_height;
+    }
+    if self.hex_input {
+      height += self.gap + hex_height;
+    }
+    height
+  }
+
+  fn layout(&self, rect: Rect, hex_height: f32) -> ColorPickerLayout {
+    let square_height = (rect.size.y - self.bars_height(hex_height)).max(0.);
+    let mut y = rect.position.y + square_height + self.gap;
+    let mut next = |height: f32| {
+      let part = Rect::new(vec2(rect.position.x, y), vec2(rect.size.x, height));
+      y += height + self.gap;
+      part
+    };
+    ColorPickerLayout {
+      square: Rect::new(rect.position, vec2(rect.size.x, square_height)),
+      hue: next(self.bar_height),
+      alpha: self.alpha.then(|| next(self.bar_height)),
+      hex: self.hex_input.then(|| next(hex_height)),
+    }
+  }
+
+  fn draw_checkerboard(&self, target: &mut PaintList, rect: Rect) {
+    if self.checker_size.is_nan() || self.checker_size <= 0. {
+      return
+    }
+    self.checker_colors.0.draw(target, rect);
+    let cells = (rect.size / self.checker_size).ceil().as_uvec2();
+    for y in 0..cells.y {
+      for x in 0..cells.x {
+        if (x + y) % 2 == 0 {
+          continue
+        }
+        let offset = vec2(x as f32, y as f32) * self.checker_size;
+        self.checker_colors.1.draw(target, Rect::new(
+          rect.position + offset,
+          Vec2::splat(self.checker_size).min(rect.size - offset),
+        ));
+      }
+    }
+  }
+
+  /// Draw a marker at the given relative position along the bar
+  fn draw_marker(&self, target: &mut PaintList, rect: Rect, position: f32) {
+    self.marker.draw(target, Rect::new(
+      rect.position + vec2(position * (rect.size.x - self.marker_width), -2.),
+      vec2(self.marker_width, rect.size.y + 4.),
+    ));
+  }
+}
+
+impl UiElement for ColorPicker {
+  fn name(&self) -> &'static str {
+    "color_picker"
+  }
+
+  fn size(&self) -> Option<Size2d> {
+    Some(self.size)
+  }
+
+  fn measure(&self, ctx: MeasureContext) -> Response {
+    let width = compute_size(ctx.layout, self.size, vec2(Self::DEFAULT_WIDTH, 0.)).x;
+    let hex_height = self.hex_height(ctx.painter, ctx.current_font);
+    Response {
+      size: compute_size(ctx.layout, self.size, vec2(width, width + self.bars_height(hex_height))),
+      ..Default::default()
+    }
+  }
+
+  fn process(&self, ctx: ProcessContext) {
+    let rect = ctx.measure.rect(ctx.layout.position);
+    let hex_height = self.hex_height(ctx.painter, ctx.current_font);
+    let layout = self.layout(rect, hex_height);
+
+    let state = ctx.state.acquire_mut_or_default::<ColorPickerState>(self.id);
+    state.sync(self.value);
+    let mut hsv = state.hsv;
+    let mut alpha = self.value.w;
+    let mut new_value = None;
+    let mut hex_value = None;
+
+    //Dragging within the square, hue strip or the alpha bar
+    if let Some(res) = ctx.input.check_active(layout.square) {
+      let uv = (res.position_in_rect / layout.square.size).clamp(Vec2::ZERO, Vec2::ONE);
+      hsv.y = uv.x;
+      hsv.z = 1. - uv.y;
+      new_value = Some(color::hsva(hsv.x, hsv.y, hsv.z, alpha));
+    }
+    if let Some(res) = ctx.input.check_active(layout.hue) {
+      hsv.x = (res.position_in_rect.x / layout.hue.size.x).clamp(0., 1.);
+      new_value = Some(color::hsva(hsv.x, hsv.y, hsv.z, alpha));
+    }
+    if let Some(alpha_rect) = layout.alpha {
+      if let Some(res) = ctx.input.check_active(alpha_rect) {
+        alpha = (res.position_in_rect.x / alpha_rect.size.x).clamp(0., 1.);
+        new_value = Some(color::hsva(hsv.x, hsv.y, hsv.z, alpha));
+      }
+    }
+
+    //Hex input
+    //The color is updated as soon as the input contains a valid hex string
+    if let Some(hex_rect) = layout.hex {
+      let hex = &mut state.hex;
+      hex.update_focus(&ctx.input, hex_rect, &format_hex(self.value, self.alpha));
+      if hex.focused {
+        let mut changed = false;
+        for event in ctx.input.events() {
+          match *event {
+            UiEvent::KeyboardButton { key: KeyboardKey::Enter | KeyboardKey::NpEnter, state: ButtonState::Pressed } => {
+              hex.focused = false;
+            },
+            UiEvent::KeyboardButton { key, state: ButtonState::Pressed } => {
+              changed |= hex.handle_key(key, &ctx.input);
+            },
+            UiEvent::TextInput(c) if c.is_ascii_hexdigit() || c == '#' => {
+              hex.insert_char(c);
+              changed = true;
+            },
+            _ => (),
+          }
+        }
+        if let (true, Some(color)) = (changed, parse_hex(&hex.buffer)) {
+          hex_value = Some(if self.alpha { color } else { color.with_w(alpha) });
+        }
+        if let Some(res) = ctx.input.check_active(hex_rect) {
+          let offsets = self.paint_text(hex.buffer.clone(), ctx.current_font).caret_offsets(ctx.painter);
+          let index = closest_caret(&offsets, res.position_in_rect.x - self.hex_padding.left);
+          hex.move_caret(index, !ctx.input.mouse_button_just_pressed(MouseButton::Primary));
+        }
+      }
+    }
+
+    //Keep the HSV values set by dragging (as they may contain the hue and saturation lost in the conversion)
+    if let Some(value) = new_value {
+      state.hsv = hsv;
+      state.synced = Some(value);
+    }
+    if let Some(value) = hex_value {
+      state.sync(value);
+    }
+    if let Some(value) = hex_value.or(new_value) {
+      if let Some(signal) = &self.on_change {
+        signal.fire(ctx.signal, value);
+      }
+    }
+    let hsv = state.hsv;
+    let value = state.synced.unwrap_or(self.value);
+
+    //Saturation/value square
+    //The quad is drawn as two triangles, so the colors are only interpolated linearly within each of them
+    //This matches the HSV model exactly along the edges, and approximates it inside of the square
+    FillColor::from_corners(Corners {
+      top_left: color::WHITE,
+      top_right: color::hsva(hsv.x, 1., 1., 1.),
+      bottom_left: color::BLACK,
+      bottom_right: color::BLACK,
+    }).draw(ctx.paint_target, layout.square);
+    self.handle.draw(ctx.paint_target, Rect::new(
+      layout.square.position + layout.square.size * vec2(hsv.y, 1. - hsv.z) - Vec2::splat(self.handle_size / 2.),
+      Vec2::splat(self.handle_size),
+    ));
+
+    //Hue strip, made out of 6 gradients between the primary and secondary colors
+    let segment_width = layout.hue.size.x / 6.;
+    for segment in 0..6 {
+      let left = color::hsva(segment as f32 / 6., 1., 1., 1.);
+      let right = color::hsva((segment + 1) as f32 / 6., 1., 1., 1.);
+      FillColor::from_corners(Corners {
+        top_left: left,
+        top_right: right,
+        bottom_left: left,
+        bottom_right: right,
+      }).draw(ctx.paint_target, Rect::new(
+        layout.hue.position + vec2(segment as f32 * segment_width, 0.),
+        vec2(segment_width, layout.hue.size.y),
+      ));
+    }
+    self.draw_marker(ctx.paint_target, layout.hue, hsv.x);
+
+    //Alpha bar
+    if let Some(alpha_rect) = layout.alpha {
+      self.draw_checkerboard(ctx.paint_target, alpha_rect);
+      let rgb = vec3(value.x, value.y, value.z);
+      FillColor::from_corners(Corners {
+        top_left: rgb.extend(0.),
+        top_right: rgb.extend(1.),
+        bottom_left: rgb.extend(0.),
+        bottom_right: rgb.extend(1.),
+      }).draw(ctx.paint_target, alpha_rect);
+      self.draw_marker(ctx.paint_target, alpha_rect, value.w);
+    }
+
+    //Hex input field
+    if let Some(hex_rect) = layout.hex {
+      let hex = &ctx.state.acquire_mut_or_default::<ColorPickerState>(self.id).hex;
+      if hex.focused {
+        self.hex_background_focused.draw(ctx.paint_target, hex_rect);
+      } else {
+        self.hex_background.draw(ctx.paint_target, hex_rect);
+      }
+      let text_position = hex_rect.position + vec2(self.hex_padding.left, self.hex_padding.top);
+      ctx.paint_target.add(PaintTransform {
+        transform: Affine2::from_translation(text_position),
+        child: self.paint_text(hex.buffer.clone(), ctx.current_font),
+      });
+      if hex.focused {
+        let offsets = self.paint_text(hex.buffer.clone(), ctx.current_font).caret_offsets(ctx.painter);
+        self.caret.draw(ctx.paint_target, Rect::new(
+          text_position + vec2(offsets[hex.caret], 0.),
+          vec2(1., hex_rect.size.y - self.hex_padding.sum_vertical()),
+        ));
+      }
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_format_hex() {
+    assert_eq!(format_hex(vec4(1., 0.5, 0., 0.25), false), "#FF8000");
+    assert_eq!(format_hex(vec4(1., 0.5, 0., 0.25), true), "#FF800040");
+    assert_eq!(format_hex(vec4(2., -1., 0., 1.), true), "#FF0000FF");
+  }
+
+  #[test]
+  fn test_parse_hex() {
+    assert_eq!(parse_hex("#FF0000"), Some(vec4(1., 0., 0., 1.)));
+    assert_eq!(parse_hex("  00ff00 "), Some(vec4(0., 1., 0., 1.)));
+    assert_eq!(parse_hex("#0000FF00"), Some(vec4(0., 0., 1., 0.)));
+  }
+
+  #[test]
+  fn test_parse_hex_invalid() {
+    assert_eq!(parse_hex(""), None);
+    assert_eq!(parse_hex("#FFF"), None);
+    assert_eq!(parse_hex("#GGGGGG"), None);
+    assert_eq!(parse_hex("#+FFFFF"), None);
+    assert_eq!(parse_hex("#FF0000FF00"), None);
+  }
+
+  #[test]
+  fn test_hex_round_trip() {
+    for text in ["#000000", "#FFFFFF", "#12AB7F", "#FEDCBA98", "#00000000"] {
+      let color = parse_hex(text).unwrap();
+      assert_eq!(format_hex(color, text.len() == 9), text);
+    }
+  }
+}
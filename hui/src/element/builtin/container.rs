@@ -496,10 +496,14 @@ impl UiElement for Container {
           measure: &el_measure,
           layout: &el_layout,
           paint_target: ctx.paint_target,
+          paint_overlay: ctx.paint_overlay,
           state: ctx.state,
           current_font: ctx.current_font,
           input: ctx.input,
           signal: ctx.signal,
+          time: ctx.time,
+          root_rect: ctx.root_rect,
+          overlay_occluders: ctx.overlay_occluders,
         });
 
         //layout
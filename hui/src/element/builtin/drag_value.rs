@@ -0,0 +1,390 @@
+//! a numeric value element that can be changed by dragging, or typed in directly
+
+use core::hash::Hash;
+use alloc::{borrow::Cow, boxed::Box, string::String};
+use derive_setters::Setters;
+use glam::{vec2, Affine2, Vec4};
+use hui_painter::{
+  paint::command::{text::PaintText, PaintCommand, PaintTransform},
+  text::FontHandle,
+  PainterInstance,
+};
+use crate::{
+  element::{text_input::{closest_caret, TextInputState}, MeasureContext, ProcessContext, UiElement},
+  event::UiEvent,
+  frame::{Frame, RectFrame},
+  input::{ButtonState, KeyboardKey, MouseButton},
+  layout::{compute_size, Size, Size2d},
+  measure::Response,
+  rect::{Rect, Sides},
+  signal::{trigger::SignalTriggerArg, Signal},
+  state::{state_id, State, StateId},
+};
+
+/// State of a [`DragValue`] element
+#[derive(Default)]
+pub struct DragValueState {
+  /// Unrounded value accumulated while dragging (`None` if not currently dragged)
+  pub drag_value: Option<f64>,
+
+  /// Whether the pointer moved far enough since the button was pressed for the input to count as a drag
+  pub dragged: bool,
+
+  /// Editing state of the text entry (the element is in text entry mode while it's focused)
+  pub text: TextInputState,
+}
+
+impl State for DragValueState {}
+
+/// A numeric value element that can be changed by dragging it horizontally
+///
+/// Holding shift while dragging changes the value in fine steps, holding control in coarse steps\
+/// Clicking the element without dragging switches it to text entry mode,
+/// where the value can be typed in directly (confirmed with Enter, cancelled with Escape)
+///
+/// The value is set by the [`DragValue::value`], so you should update it in response to the `on_change` signal
+#[derive(Setters)]
+#[setters(no_std, prefix = "with_")]
+pub struct DragValue {
+  /// Id used to store the state of the element
+  #[setters(skip)]
+  pub id: StateId,
+
+  /// Current value
+  pub value: f64,
+
+  /// Minimum value
+  pub min: f64,
+
+  /// Maximum value
+  pub max: f64,
+
+  /// Step the value is snapped to\
+  /// If set to `0.0`, the value is not snapped
+  pub step: f64,
+
+  /// Change of the value per pixel dragged
+  pub speed: f64,
+
+  /// Speed multiplier used while holding shift
+  pub fine_factor: f64,
+
+  /// Speed multiplier used while holding control
+  pub coarse_factor: f64,
+
+  /// Number of digits displayed after the decimal point
+  pub precision: usize,
+
+  /// Text displayed before the value
+  #[setters(into)]
+  pub prefix: Cow<'static, str>,
+
+  /// Text displayed after the value
+  #[setters(into)]
+  pub suffix: Cow<'static, str>,
+
+  /// Size of the element
+  #[setters(into)]
+  pub size: Size2d,
+
+  /// Padding inside the element (distance from the edges to the text)
+  #[setters(into)]
+  pub padding: Sides<f32>,
+
+  /// Color of the text
+  #[setters(into)]
+  pub color: Vec4,
+
+  /// Font to use for rendering the text\
+  /// If set to `None` either currently selected font or the default font will be used
+  #[setters(into)]
+  pub font: Option<FontHandle>,
+
+  /// Size of the text, in points (these are not pixels)
+  pub text_size: f32,
+
+  /// Background frame
+  #[setters(skip)]
+  pub background: Box<dyn Frame>,
+
+  /// Background frame used while the mouse pointer is over the element
+  #[setters(skip)]
+  pub background_hovered: Box<dyn Frame>,
+
+  /// Background frame used while the value is being dragged
+  #[setters(skip)]
+  pub background_active: Box<dyn Frame>,
+
+  /// Background frame used in text entry mode
+  #[setters(skip)]
+  pub background_editing: Box<dyn Frame>,
+
+  /// Caret frame used in text entry mode
+  #[setters(skip)]
+  pub caret: Box<dyn Frame>,
+
+  /// Frame drawn behind the selected text in text entry mode
+  #[setters(skip)]
+  pub selection: Box<dyn Frame>,
+
+  /// Signal that will be fired with the new value whenever it changes
+  #[setters(skip)]
+  pub on_change: Option<SignalTriggerArg<f64>>,
+}
+
+impl DragValue {
+  /// Distance (in pixels) the pointer has to move before the input counts as a drag rather than a click
+  pub const DRAG_THRESHOLD: f32 = 3.;
+
+  pub fn new(id: impl Hash, value: f64) -> Self {
+    Self {
+      id: state_id(id),
+      value,
+      min: f64::NEG_INFINITY,
+      max: f64::INFINITY,
+      step: 0.,
+      speed: 0.1,
+      fine_factor: 0.1,
+      coarse_factor: 10.,
+      precision: 2,
+      prefix: "".into(),
+      suffix: "".into(),
+      size: (Size::Auto, Size::Auto).into(),
+      padding: Sides::horizontal_vertical(5., 3.),
+      color: Vec4::new(1., 1., 1., 1.),
+      font: None,
+      text_size: 16.,
+      background: Box::new(RectFrame::color((0.2, 0.2, 0.2))),
+      background_hovered: Box::new(RectFrame::color((0.25, 0.25, 0.25))),
+      background_active: Box::new(RectFrame::color((0.3, 0.3, 0.3))),
+      background_editing: Box::new(RectFrame::color((0.1, 0.1, 0.1))),
+      caret: Box::new(RectFrame::color((1., 1., 1.))),
+      selection: Box::new(RectFrame::color((0.2, 0.4, 0.8, 0.5))),
+      on_change: None,
+    }
+  }
+
+  /// Set both the minimum and maximum value
+  pub fn with_range(self, min: f64, max: f64) -> Self {
+    Self { min, max, ..self }
+  }
+
+  pub fn on_change<S: Signal, T: Fn(f64) -> S + 'static>(self, f: T) -> Self {
+    Self {
+      on_change: Some(SignalTriggerArg::new(f)),
+      ..self
+    }
+  }
+
+  pub fn with_background(mut self, frame: impl Frame + 'static) -> Self {
+    self.background = Box::new(frame);
+    self
+  }
+
+  pub fn with_background_hovered(mut self, frame: impl Frame + 'static) -> Self {
+    self.background_hovered = Box::new(frame);
+    self
+  }
+
+  pub fn with_background_active(mut self, frame: impl Frame + 'static) -> Self {
+    self.background_active = Box::new(frame);
+    self
+  }
+
+  pub fn with_background_editing(mut self, frame: impl Frame + 'static) -> Self {
+    self.background_editing = Box::new(frame);
+    self
+  }
+
+  pub fn with_caret(mut self, frame: impl Frame + 'static) -> Self {
+    self.caret = Box::new(frame);
+    self
+  }
+
+  pub fn with_selection(mut self, frame: impl Frame + 'static) -> Self {
+    self.selection = Box::new(frame);
+    self
+  }
+
+  /// Snap the value to the step and clamp it to the range
+  fn constrain(&self, value: f64) -> f64 {
+    let value = match self.step > 0. {
+      true => (value / self.step).round() * self.step,
+      false => value,
+    };
+    value.max(self.min).min(self.max)
+  }
+
+  /// Format the value without the prefix and suffix
+  fn format_value(&self, value: f64) -> String {
+    format!("{:.*}", self.precision, value)
+  }
+
+  fn paint_text(&self, text: impl Into<Cow<'static, str>>, current_font: FontHandle) -> PaintText {
+    PaintText::new(text, self.font.unwrap_or(current_font), self.text_size, self.color)
+  }
+
+  fn line_height(&self, painter: &PainterInstance, current_font: FontHandle) -> f32 {
+    self.paint_text("", current_font).line_height(painter)
+  }
+
+  fn display_text(&self) -> String {
+    format!("{}{}{}", self.prefix, self.format_value(self.value), self.suffix)
+  }
+}
+
+impl UiElement for DragValue {
+  fn name(&self) -> &'static str {
+    "drag_value"
+  }
+
+  fn size(&self) -> Option<Size2d> {
+    Some(self.size)
+  }
+
+  fn measure(&self, ctx: MeasureContext) -> Response {
+    let text_width = self.paint_text(self.display_text(), ctx.current_font).bounds(ctx.painter).size.x;
+    Response {
+      size: compute_size(ctx.layout, self.size, vec2(
+        text_width + self.padding.sum_horizontal(),
+        self.line_height(ctx.painter, ctx.current_font) + self.padding.sum_vertical(),
+      )),
+      ..Default::default()
+    }
+  }
+
+  fn process(&self, ctx: ProcessContext) {
+    let rect = ctx.measure.rect(ctx.layout.position);
+    let inner = Rect {
+      position: rect.position + self.padding.top_left(),
+      size: rect.size - self.padding.sum_vec(),
+    };
+
+    let state = ctx.state.acquire_mut_or_default::<DragValueState>(self.id);
+    let mut new_value = None;
+
+    if state.text.focused {
+      //Text entry mode
+      //Clicking outside of the element confirms the entered value
+      let mut confirm = false;
+      if ctx.input.mouse_button_just_pressed(MouseButton::Primary) && !ctx.input.check_hover(rect) {
+        state.text.focused = false;
+        confirm = true;
+      }
+      for event in ctx.input.events() {
+        if !state.text.focused {
+          break
+        }
+        match *event {
+          UiEvent::KeyboardButton { key: KeyboardKey::Enter | KeyboardKey::NpEnter, state: ButtonState::Pressed } => {
+            state.text.focused = false;
+            confirm = true;
+          },
+          UiEvent::KeyboardButton { key, state: ButtonState::Pressed } => {
+            state.text.handle_key(key, &ctx.input);
+          },
+          UiEvent::TextInput(c) if c.is_ascii_digit() || matches!(c, '.' | '-' | '+' | 'e' | 'E') => {
+            state.text.insert_char(c);
+          },
+          _ => (),
+        }
+      }
+      if confirm {
+        //Invalid input is discarded
+        if let Ok(value) = state.text.buffer.trim().parse::<f64>() {
+          new_value = Some(self.constrain(value));
+        }
+      } else if let Some(res) = ctx.input.check_active(rect) {
+        let offsets = self.paint_text(state.text.buffer.clone(), ctx.current_font).caret_offsets(ctx.painter);
+        let index = closest_caret(&offsets, res.position_in_rect.x - self.padding.left);
+        state.text.move_caret(index, !ctx.input.mouse_button_just_pressed(MouseButton::Primary));
+      }
+    } else {
+      //Dragging
+      if ctx.input.mouse_button_just_pressed(MouseButton::Primary) && ctx.input.check_hover(rect) {
+        state.drag_value = Some(self.value);
+        state.dragged = false;
+      }
+      match (state.drag_value, ctx.input.check_active(rect)) {
+        (Some(drag_value), Some(res)) => {
+          if (res.position_in_rect - res.start_position_in_rect).length() > Self::DRAG_THRESHOLD {
+            state.dragged = true;
+          }
+          if state.dragged {
+            let factor = if ctx.input.shift_down() {
+              self.fine_factor
+            } else if ctx.input.control_down() {
+              self.coarse_factor
+            } else {
+              1.
+            };
+            let delta = (res.position_in_rect.x - res.last_position_in_rect.x) as f64;
+            //Unclamped value is kept, so that dragging past the range and back doesn't move the value immediately
+            let drag_value = drag_value + delta * self.speed * factor;
+            state.drag_value = Some(drag_value);
+            let value = self.constrain(drag_value);
+            if value != self.value {
+              new_value = Some(value);
+            }
+          }
+        },
+        _ => state.drag_value = None,
+      }
+
+      //Clicking without dragging switches to text entry mode, with the whole value selected
+      if ctx.input.check_click(rect).is_some() && !state.dragged {
+        let text = &mut state.text;
+        text.focused = true;
+        text.buffer = self.format_value(self.value);
+        text.selection_anchor = Some(0);
+        text.caret = text.len();
+        text.scroll = 0.;
+      }
+    }
+
+    if let Some(value) = new_value {
+      if let Some(signal) = &self.on_change {
+        signal.fire(ctx.signal, value);
+      }
+    }
+
+    //Draw the background
+    let background = if state.text.focused {
+      &self.background_editing
+    } else if state.drag_value.is_some() {
+      &self.background_active
+    } else if ctx.input.check_hover(rect) {
+      &self.background_hovered
+    } else {
+      &self.background
+    };
+    background.draw(ctx.paint_target, rect);
+
+    //Draw the text
+    if state.text.focused {
+      let offsets = self.paint_text(state.text.buffer.clone(), ctx.current_font).caret_offsets(ctx.painter);
+      if let Some(range) = state.text.selection() {
+        self.selection.draw(ctx.paint_target, Rect {
+          position: inner.position + vec2(offsets[range.start], 0.),
+          size: vec2(offsets[range.end] - offsets[range.start], inner.size.y),
+        });
+      }
+      ctx.paint_target.add(PaintTransform {
+        transform: Affine2::from_translation(inner.position),
+        child: self.paint_text(state.text.buffer.clone(), ctx.current_font),
+      });
+      self.caret.draw(ctx.paint_target, Rect {
+        position: inner.position + vec2(offsets[state.text.caret], 0.),
+        size: vec2(1., inner.size.y),
+      });
+    } else if self.color.w > 0. {
+      //Value is centered within the element
+      let text = self.paint_text(self.display_text(), ctx.current_font);
+      let width = text.bounds(ctx.painter).size.x;
+      ctx.paint_target.add(PaintTransform {
+        transform: Affine2::from_translation(inner.position + vec2(((inner.size.x - width) / 2.).max(0.).round(), 0.)),
+        child: text,
+      });
+    }
+  }
+}
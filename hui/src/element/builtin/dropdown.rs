@@ -0,0 +1,302 @@
+//! a dropdown (combo box) element that allows picking one of multiple options from a popup list
+
+use core::hash::Hash;
+use alloc::{borrow::Cow, boxed::Box, vec::Vec};
+use derive_setters::Setters;
+use glam::{vec2, Affine2, Vec4};
+use hui_painter::{
+  paint::command::{text::PaintText, PaintCommand, PaintList, PaintTransform},
+  text::FontHandle,
+  PainterInstance,
+};
+use crate::{
+  element::{MeasureContext, ProcessContext, UiElement},
+  frame::{Frame, RectFrame},
+  input::MouseButton,
+  layout::{compute_size, Size, Size2d},
+  measure::Response,
+  rect::{Rect, Sides},
+  signal::{trigger::SignalTriggerArg, Signal},
+  state::{state_id, State, StateId},
+};
+
+/// State of a [`Dropdown`] element
+///
+/// Stored in the global scope of the [`StateRepo`](crate::state::StateRepo),
+/// as the popup is not a part of the element hierarchy
+#[derive(Clone, Copy, Debug, Default)]
+pub struct DropdownState {
+  /// Whether the popup list is currently open
+  pub open: bool,
+}
+
+impl State for DropdownState {}
+
+/// A dropdown (combo box) element that allows picking one of multiple options
+///
+/// Clicking the element opens a popup list with all of the options,
+/// which is drawn on top of everything else rendered on the same layer\
+/// The popup gets closed once an option is picked, or the user clicks anywhere outside of it
+#[derive(Setters)]
+#[setters(no_std, prefix = "with_")]
+pub struct Dropdown {
+  /// Id used to store the state of the element
+  #[setters(skip)]
+  pub id: StateId,
+
+  /// List of options to choose from
+  pub options: Vec<Cow<'static, str>>,
+
+  /// Index of the currently selected option
+  ///
+  /// If set to `None` or out of range, the placeholder text will be displayed instead
+  #[setters(into)]
+  pub selected: Option<usize>,
+
+  /// Text displayed if no option is selected
+  #[setters(into)]
+  pub placeholder: Cow<'static, str>,
+
+  /// Size of the element
+  #[setters(into)]
+  pub size: Size2d,
+
+  /// Padding inside the element and each of the popup items
+  #[setters(into)]
+  pub padding: Sides<f32>,
+
+  /// Color of the text
+  #[setters(into)]
+  pub color: Vec4,
+
+  /// Color of the placeholder text
+  #[setters(into)]
+  pub placeholder_color: Vec4,
+
+  /// Font to use for rendering the text\
+  /// If set to `None` either currently selected font or the default font will be used
+  #[setters(into)]
+  pub font: Option<FontHandle>,
+
+  /// Size of the text, in points (these are not pixels)
+  pub text_size: f32,
+
+  /// Frame used while the dropdown is not being interacted with
+  #[setters(skip)]
+  pub frame: Box<dyn Frame>,
+
+  /// Frame used while the mouse pointer is over the dropdown
+  #[setters(skip)]
+  pub frame_hovered: Box<dyn Frame>,
+
+  /// Frame used while the popup is open
+  #[setters(skip)]
+  pub frame_open: Box<dyn Frame>,
+
+  /// Background frame of the popup list
+  #[setters(skip)]
+  pub popup: Box<dyn Frame>,
+
+  /// Frame drawn behind the popup item under the mouse pointer
+  #[setters(skip)]
+  pub item_hovered: Box<dyn Frame>,
+
+  /// Frame drawn behind the currently selected popup item
+  #[setters(skip)]
+  pub item_selected: Box<dyn Frame>,
+
+  /// Signal that will be fired with the index of the option when it gets picked
+  #[setters(skip)]
+  pub on_change: Option<SignalTriggerArg<usize>>,
+}
+
+impl Dropdown {
+  pub fn new(id: impl Hash) -> Self {
+    Self {
+      id: state_id(id),
+      options: Vec::new(),
+      selected: None,
+      placeholder: "".into(),
+      size: (Size::Auto, Size::Auto).into(),
+      padding: Sides::horizontal_vertical(10., 5.),
+      color: Vec4::new(1., 1., 1., 1.),
+      placeholder_color: Vec4::new(1., 1., 1., 0.5),
+      font: None,
+      text_size: 16.,
+      frame: Box::new(RectFrame::color((0.25, 0.25, 0.25))),
+      frame_hovered: Box::new(RectFrame::color((0.35, 0.35, 0.35))),
+      frame_open: Box::new(RectFrame::color((0.15, 0.15, 0.15))),
+      popup: Box::new(RectFrame::color((0.2, 0.2, 0.2))),
+      item_hovered: Box::new(RectFrame::color((0.35, 0.35, 0.35))),
+      item_selected: Box::new(RectFrame::color((0.0, 0.0, 0.75))),
+      on_change: None,
+    }
+  }
+
+  pub fn on_change<S: Signal, T: Fn(usize) -> S + 'static>(self, f: T) -> Self {
+    Self {
+      on_change: Some(SignalTriggerArg::new(f)),
+      ..self
+    }
+  }
+
+  pub fn with_frame(mut self, frame: impl Frame + 'static) -> Self {
+    self.frame = Box::new(frame);
+    self
+  }
+
+  pub fn with_frame_hovered(mut self, frame: impl Frame + 'static) -> Self {
+    self.frame_hovered = Box::new(frame);
+    self
+  }
+
+  pub fn with_frame_open(mut self, frame: impl Frame + 'static) -> Self {
+    self.frame_open = Box::new(frame);
+    self
+  }
+
+  pub fn with_popup(mut self, frame: impl Frame + 'static) -> Self {
+    self.popup = Box::new(frame);
+    self
+  }
+
+  pub fn with_item_hovered(mut self, frame: impl Frame + 'static) -> Self {
+    self.item_hovered = Box::new(frame);
+    self
+  }
+
+  pub fn with_item_selected(mut self, frame: impl Frame + 'static) -> Self {
+    self.item_selected = Box::new(frame);
+    self
+  }
+
+  fn paint_text(&self, text: impl Into<Cow<'static, str>>, color: Vec4, current_font: FontHandle) -> PaintText {
+    PaintText::new(text, self.font.unwrap_or(current_font), self.text_size, color)
+  }
+
+  fn line_height(&self, painter: &PainterInstance, current_font: FontHandle) -> f32 {
+    self.paint_text("", self.color, current_font).line_height(painter)
+  }
+
+  /// Draw the text, vertically centered within the rect
+  fn draw_text(&self, target: &mut PaintList, text: Cow<'static, str>, color: Vec4, rect: Rect, line_height: f32, current_font: FontHandle) {
+    if text.is_empty() || color.w == 0. {
+      return
+    }
+    target.add(PaintTransform {
+      transform: Affine2::from_translation(rect.position + vec2(
+        self.padding.left,
+        ((rect.size.y - line_height) / 2.).round(),
+      )),
+      child: self.paint_text(text, color, current_font),
+    });
+  }
+}
+
+impl UiElement for Dropdown {
+  fn name(&self) -> &'static str {
+    "dropdown"
+  }
+
+  fn size(&self) -> Option<Size2d> {
+    Some(self.size)
+  }
+
+  fn measure(&self, ctx: MeasureContext) -> Response {
+    //Make the element wide enough to fit the longest option
+    let text_width = self.options.iter()
+      .chain(core::iter::once(&self.placeholder))
+      .map(|text| self.paint_text(text.clone(), self.color, ctx.current_font).bounds(ctx.painter).size.x)
+      .fold(0., f32::max);
+    Response {
+      size: compute_size(ctx.layout, self.size, vec2(
+        text_width + self.padding.sum_horizontal(),
+        self.line_height(ctx.painter, ctx.current_font) + self.padding.sum_vertical(),
+      )),
+      ..Default::default()
+    }
+  }
+
+  fn process(&self, ctx: ProcessContext) {
+    let rect = ctx.measure.rect(ctx.layout.position);
+    let line_height = self.line_height(ctx.painter, ctx.current_font);
+    let item_height = line_height + self.padding.sum_vertical();
+
+    //The popup is not a part of the element hierarchy, so it's state is stored in the global scope
+    let mut open = ctx.state.global(|state| {
+      state.acquire_or_default::<DropdownState>(self.id).open
+    });
+
+    //Draw the element itself
+    let frame = if open {
+      &self.frame_open
+    } else if ctx.input.check_hover(rect) {
+      &self.frame_hovered
+    } else {
+      &self.frame
+    };
+    frame.draw(ctx.paint_target, rect);
+    match self.selected.and_then(|idx| self.options.get(idx)) {
+      Some(text) => self.draw_text(ctx.paint_target, text.clone(), self.color, rect, line_height, ctx.current_font),
+      None => self.draw_text(ctx.paint_target, self.placeholder.clone(), self.placeholder_color, rect, line_height, ctx.current_font),
+    }
+
+    //Place the popup below the element, or above it if there's not enough space below,
+    //keeping it within the root rect
+    let root = ctx.root_rect;
+    let size = vec2(rect.size.x, item_height * self.options.len() as f32);
+    let space_below = root.position.y + root.size.y - (rect.position.y + rect.size.y);
+    let space_above = rect.position.y - root.position.y;
+    let popup_y = match space_below < size.y && space_above > space_below {
+      true => rect.position.y - size.y,
+      false => rect.position.y + rect.size.y,
+    };
+    let popup_rect = Rect::new(
+      vec2(rect.position.x, popup_y).min(root.position + root.size - size).max(root.position),
+      size,
+    );
+
+    if open {
+      //Draw the popup and handle the input
+      //The popup blocks the input to the elements below it
+      let popup_input = ctx.input.overlay();
+      ctx.overlay_occluders.push(popup_rect);
+      self.popup.draw(ctx.paint_overlay, popup_rect);
+      for (idx, text) in self.options.iter().enumerate() {
+        let item_rect = Rect {
+          position: popup_rect.position + vec2(0., item_height * idx as f32),
+          size: vec2(popup_rect.size.x, item_height),
+        };
+        if self.selected == Some(idx) {
+          self.item_selected.draw(ctx.paint_overlay, item_rect);
+        }
+        if popup_input.check_hover(item_rect) {
+          self.item_hovered.draw(ctx.paint_overlay, item_rect);
+        }
+        self.draw_text(ctx.paint_overlay, text.clone(), self.color, item_rect, line_height, ctx.current_font);
+        if popup_input.check_click(item_rect).is_some() {
+          if let Some(signal) = &self.on_change {
+            signal.fire(ctx.signal, idx);
+          }
+          open = false;
+        }
+      }
+
+      //Close the popup if the user clicks anywhere outside of it
+      if popup_input.mouse_button_just_pressed(MouseButton::Primary) &&
+        !popup_input.check_hover(rect) &&
+        !popup_input.check_hover(popup_rect)
+      {
+        open = false;
+      }
+    }
+
+    if ctx.input.check_click(rect).is_some() {
+      open = !open;
+    }
+
+    ctx.state.global(|state| {
+      state.acquire_mut_or_default::<DropdownState>(self.id).open = open;
+    });
+  }
+}
@@ -0,0 +1,391 @@
+//! a flexbox-like container element that distributes free space between its children
+
+use alloc::{boxed::Box, vec::Vec};
+use derive_setters::Setters;
+use glam::{vec2, Vec2};
+use crate::{
+  element::{ElementList, MeasureContext, ProcessContext, UiElement},
+  frame::{Frame, RectFrame},
+  layout::{compute_size, Direction, LayoutInfo, Size, Size2d},
+  measure::Response,
+  rect::Sides,
+};
+
+/// Distribution of the free space along the primary axis of a [`Flex`] container
+///
+/// Only applies if none of the children can grow to fill the free space
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum FlexJustify {
+  /// Pack the children at the beginning of the axis
+  #[default]
+  Start,
+
+  /// Pack the children in the center of the axis
+  Center,
+
+  /// Pack the children at the end of the axis
+  End,
+
+  /// First child at the beginning, last at the end, free space evenly distributed between the children
+  SpaceBetween,
+
+  /// Free space evenly distributed around each child (half-sized space at the edges)
+  SpaceAround,
+
+  /// Free space evenly distributed between the children and the edges
+  SpaceEvenly,
+}
+
+/// Alignment of the children along the secondary axis of a [`Flex`] container
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum FlexAlign {
+  /// Align the children to the beginning of the axis
+  #[default]
+  Start,
+
+  /// Center the children on the axis
+  Center,
+
+  /// Align the children to the end of the axis
+  End,
+
+  /// Stretch the children to fill the container
+  Stretch,
+}
+
+/// Flex parameters of a single [`Flex`] child
+#[derive(Setters, Clone, Copy, Debug, PartialEq)]
+#[setters(no_std, prefix = "with_")]
+pub struct FlexItem {
+  /// Ratio of the free space the child takes up (relative to other children)
+  ///
+  /// If set to `0.0`, the child will not grow
+  pub grow: f32,
+
+  /// Ratio of the overflow the child absorbs if there's not enough space (weighted by its basis size)
+  ///
+  /// If set to `0.0`, the child will not shrink
+  pub shrink: f32,
+
+  /// Initial size of the child on the primary axis, in pixels, before the free space is distributed
+  ///
+  /// If set to `None`, the measured size of the child will be used
+  #[setters(into)]
+  pub basis: Option<f32>,
+
+  /// Overrides the [`Flex::align_items`] value for this child
+  #[setters(into)]
+  pub align_self: Option<FlexAlign>,
+}
+
+impl Default for FlexItem {
+  fn default() -> Self {
+    Self {
+      grow: 0.,
+      shrink: 1.,
+      basis: None,
+      align_self: None,
+    }
+  }
+}
+
+impl FlexItem {
+  /// Create a new `FlexItem` that grows with the given factor
+  pub fn grow(grow: f32) -> Self {
+    Self {
+      grow,
+      ..Default::default()
+    }
+  }
+}
+
+/// A single child of a [`Flex`] element
+pub struct FlexChild {
+  /// The child element
+  pub element: Box<dyn UiElement>,
+
+  /// Flex parameters of the child
+  pub item: FlexItem,
+}
+
+/// Distribute the free space on the primary axis according to the grow and shrink factors
+///
+/// Returns the primary axis size of each item, starting from its basis size\
+/// (shrink factors are weighted by the basis sizes, and the items never shrink below zero)
+fn distribute_space(items: &[FlexItem], bases: &[f32], free_space: f32) -> Vec<f32> {
+  let total_grow: f32 = items.iter().map(|item| item.grow).sum();
+  let total_shrink: f32 = items.iter().zip(bases).map(|(item, basis)| item.shrink * basis).sum();
+  items.iter().zip(bases).map(|(item, &basis)| {
+    if free_space > 0. && total_grow > 0. {
+      basis + free_space * item.grow / total_grow
+    } else if free_space < 0. && total_shrink > 0. {
+      (basis + free_space * item.shrink * basis / total_shrink).max(0.)
+    } else {
+      basis
+    }
+  }).collect()
+}
+
+struct FlexUserData {
+  /// Final response of each child (with the size overridden to the flex size) and its position relative to the container
+  children: Vec<(Response, Vec2)>,
+}
+
+/// A flexbox-like container element
+///
+/// Children are laid out in a single line along the primary axis,\
+/// and the free space is distributed between them according to their grow and shrink factors\
+/// Children are resized by overriding their measured size, and re-measuring them with the new size as the maximum size
+#[derive(Setters)]
+#[setters(no_std, prefix = "with_")]
+pub struct Flex {
+  /// Size of the container
+  #[setters(into)]
+  pub size: Size2d,
+
+  /// Layout direction (horizontal/vertical)
+  pub direction: Direction,
+
+  /// Gap between children elements
+  pub gap: f32,
+
+  /// Padding inside the container (distance from the edges to the children elements)
+  #[setters(into)]
+  pub padding: Sides<f32>,
+
+  /// Distribution of the free space along the primary axis
+  pub justify: FlexJustify,
+
+  /// Alignment of the children along the secondary axis
+  pub align_items: FlexAlign,
+
+  #[setters(skip)]
+  pub background_frame: Box<dyn Frame>,
+
+  /// List of children elements
+  #[setters(skip)]
+  pub children: Vec<FlexChild>,
+}
+
+impl Default for Flex {
+  fn default() -> Self {
+    Self {
+      size: (Size::Auto, Size::Auto).into(),
+      direction: Direction::Horizontal,
+      gap: 0.,
+      padding: Sides::all(0.),
+      justify: FlexJustify::default(),
+      align_items: FlexAlign::default(),
+      background_frame: Box::<RectFrame>::default(),
+      children: Vec::new(),
+    }
+  }
+}
+
+impl Flex {
+  /// Add children elements with default flex parameters (no grow, shrink factor of 1)
+  pub fn with_children(mut self, ui: impl FnOnce(&mut ElementList)) -> Self {
+    self.children.extend(ElementList::from_callback(ui).0.into_iter().map(|element| {
+      FlexChild { element, item: FlexItem::default() }
+    }));
+    self
+  }
+
+  /// Add a single child element with custom flex parameters
+  pub fn with_child(mut self, item: FlexItem, element: impl UiElement + 'static) -> Self {
+    self.children.push(FlexChild {
+      element: Box::new(element),
+      item,
+    });
+    self
+  }
+
+  pub fn with_background(mut self, frame: impl Frame + 'static) -> Self {
+    self.background_frame = Box::new(frame);
+    self
+  }
+
+  /// Convert a vector to (primary, secondary) axis components
+  fn split_axes(&self, v: Vec2) -> (f32, f32) {
+    match self.direction {
+      Direction::Horizontal => (v.x, v.y),
+      Direction::Vertical => (v.y, v.x),
+    }
+  }
+
+  /// Convert (primary, secondary) axis components back to a vector
+  fn join_axes(&self, pri: f32, sec: f32) -> Vec2 {
+    match self.direction {
+      Direction::Horizontal => vec2(pri, sec),
+      Direction::Vertical => vec2(sec, pri),
+    }
+  }
+}
+
+impl UiElement for Flex {
+  fn name(&self) -> &'static str {
+    "flex"
+  }
+
+  fn size(&self) -> Option<Size2d> {
+    Some(self.size)
+  }
+
+  fn measure(&self, ctx: MeasureContext) -> Response {
+    let inner_max_size = compute_size(ctx.layout, self.size, ctx.layout.max_size) - self.padding.sum_vec();
+    let child_measure = |element: &dyn UiElement, max_size: Vec2| {
+      element.measure(MeasureContext {
+        painter: ctx.painter,
+        layout: &LayoutInfo {
+          position: ctx.layout.position,
+          max_size,
+          direction: self.direction,
+          remaining_space: None,
+        },
+        state: ctx.state,
+        current_font: ctx.current_font,
+      })
+    };
+
+    //Measure the children and compute their basis sizes
+    let measures: Vec<Response> = self.children.iter()
+      .map(|child| child_measure(&*child.element, inner_max_size))
+      .collect();
+    let bases: Vec<f32> = self.children.iter().zip(&measures)
+      .map(|(child, measure)| child.item.basis.unwrap_or(self.split_axes(measure.size).0))
+      .collect();
+    let total_gap = self.gap * self.children.len().saturating_sub(1) as f32;
+    let content_pri = bases.iter().sum::<f32>() + total_gap;
+    let content_sec = measures.iter()
+      .map(|measure| self.split_axes(measure.size).1)
+      .fold(0., f32::max);
+
+    //Compute the size of the container itself
+    let size = compute_size(
+      ctx.layout,
+      self.size,
+      self.join_axes(content_pri, content_sec) + self.padding.sum_vec(),
+    );
+    let (inner_pri, inner_sec) = self.split_axes(size - self.padding.sum_vec());
+
+    //Distribute the free space on the primary axis
+    let items: Vec<FlexItem> = self.children.iter().map(|child| child.item).collect();
+    let sizes_pri = distribute_space(&items, &bases, inner_pri - content_pri);
+
+    //Distribute the leftover space (if the children didn't grow to fill it)
+    let leftover = (inner_pri - sizes_pri.iter().sum::<f32>() - total_gap).max(0.);
+    let count = self.children.len() as f32;
+    let (mut position_pri, extra_gap) = match self.justify {
+      FlexJustify::Start => (0., 0.),
+      FlexJustify::Center => (leftover / 2., 0.),
+      FlexJustify::End => (leftover, 0.),
+      FlexJustify::SpaceBetween if count > 1. => (0., leftover / (count - 1.)),
+      FlexJustify::SpaceBetween => (0., 0.),
+      FlexJustify::SpaceAround => (leftover / count / 2., leftover / count),
+      FlexJustify::SpaceEvenly => (leftover / (count + 1.), leftover / (count + 1.)),
+    };
+
+    //Compute the final size and position of each child
+    let mut children = Vec::with_capacity(self.children.len());
+    for ((child, measure), size_pri) in self.children.iter().zip(measures).zip(sizes_pri) {
+      let (measured_pri, measured_sec) = self.split_axes(measure.size);
+      let align = child.item.align_self.unwrap_or(self.align_items);
+      let size_sec = match align {
+        FlexAlign::Stretch => inner_sec,
+        _ => measured_sec,
+      };
+      let position_sec = match align {
+        FlexAlign::Start | FlexAlign::Stretch => 0.,
+        FlexAlign::Center => (inner_sec - size_sec) / 2.,
+        FlexAlign::End => inner_sec - size_sec,
+      };
+
+      //Re-measure the child if its size was changed, so that it can adapt to the new size
+      let mut measure = measure;
+      if size_pri != measured_pri || size_sec != measured_sec {
+        let new_size = self.join_axes(size_pri, size_sec);
+        measure = child_measure(&*child.element, new_size);
+        measure.size = new_size;
+      }
+
+      children.push((measure, self.padding.top_left() + self.join_axes(position_pri, position_sec)));
+      position_pri += size_pri + self.gap + extra_gap;
+    }
+
+    Response {
+      size,
+      user_data: Some(Box::new(FlexUserData { children })),
+      ..Default::default()
+    }
+  }
+
+  fn process(&self, ctx: ProcessContext) {
+    let user_data: &FlexUserData = ctx.measure.user_data
+      .as_ref().expect("no user data attached to flex")
+      .downcast_ref().expect("invalid user data type");
+
+    self.background_frame.draw(ctx.paint_target, ctx.measure.rect(ctx.layout.position));
+
+    for (child, (measure, offset)) in self.children.iter().zip(&user_data.children) {
+      child.element.process(ProcessContext {
+        painter: ctx.painter,
+        measure,
+        layout: &LayoutInfo {
+          position: ctx.layout.position + *offset,
+          max_size: measure.size,
+          direction: self.direction,
+          remaining_space: None,
+        },
+        paint_target: ctx.paint_target,
+        paint_overlay: ctx.paint_overlay,
+        state: ctx.state,
+        current_font: ctx.current_font,
+        input: ctx.input,
+        signal: ctx.signal,
+        time: ctx.time,
+        root_rect: ctx.root_rect,
+        overlay_occluders: ctx.overlay_occluders,
+      });
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_distribute_space_grow() {
+    let items = [FlexItem::grow(1.), FlexItem::grow(3.), FlexItem::default()];
+    let sizes = distribute_space(&items, &[10., 10., 10.], 40.);
+    assert_eq!(sizes, [20., 40., 10.]);
+  }
+
+  #[test]
+  fn test_distribute_space_no_grow() {
+    let items = [FlexItem::default(), FlexItem::default()];
+    let sizes = distribute_space(&items, &[10., 20.], 40.);
+    assert_eq!(sizes, [10., 20.]);
+  }
+
+  #[test]
+  fn test_distribute_space_shrink_weighted_by_basis() {
+    let items = [FlexItem::default(), FlexItem::default()];
+    let sizes = distribute_space(&items, &[10., 30.], -20.);
+    assert_eq!(sizes, [5., 15.]);
+  }
+
+  #[test]
+  fn test_distribute_space_shrink_factors() {
+    let items = [FlexItem::default().with_shrink(0.), FlexItem::default()];
+    let sizes = distribute_space(&items, &[20., 20.], -10.);
+    assert_eq!(sizes, [20., 10.]);
+  }
+
+  #[test]
+  fn test_distribute_space_never_negative() {
+    let items = [FlexItem::default(), FlexItem::default().with_shrink(0.)];
+    let sizes = distribute_space(&items, &[10., 10.], -100.);
+    assert_eq!(sizes, [0., 10.]);
+  }
+}
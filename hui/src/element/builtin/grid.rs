@@ -0,0 +1,385 @@
+//! a grid container element with row/column tracks and spanning cells
+
+use alloc::{boxed::Box, vec::Vec};
+use derive_setters::Setters;
+use glam::{vec2, Vec2};
+use crate::{
+  element::{ElementList, MeasureContext, ProcessContext, UiElement},
+  frame::{Frame, RectFrame},
+  layout::{compute_size, Alignment2d, Direction, LayoutInfo, Size, Size2d},
+  measure::Response,
+  rect::Sides,
+};
+
+/// Placement of a single [`Grid`] child
+#[derive(Setters, Clone, Copy, Debug, PartialEq, Eq)]
+#[setters(no_std, prefix = "with_")]
+pub struct GridCell {
+  /// Index of the column the child starts in
+  pub column: usize,
+
+  /// Index of the row the child starts in
+  pub row: usize,
+
+  /// Number of columns the child spans (at least 1)
+  pub column_span: usize,
+
+  /// Number of rows the child spans (at least 1)
+  pub row_span: usize,
+
+  /// Alignment of the child within the cell\
+  /// If set to `None`, the default alignment of the [`Grid`] will be used
+  #[setters(into)]
+  pub align: Option<Alignment2d>,
+}
+
+impl GridCell {
+  pub fn new(column: usize, row: usize) -> Self {
+    Self {
+      column,
+      row,
+      column_span: 1,
+      row_span: 1,
+      align: None,
+    }
+  }
+
+  /// Set both the column and row span
+  pub fn with_span(self, column_span: usize, row_span: usize) -> Self {
+    Self {
+      column_span,
+      row_span,
+      ..self
+    }
+  }
+
+  fn columns(&self) -> core::ops::Range<usize> {
+    self.column..(self.column + self.column_span.max(1))
+  }
+
+  fn rows(&self) -> core::ops::Range<usize> {
+    self.row..(self.row + self.row_span.max(1))
+  }
+}
+
+/// A single child of a [`Grid`] element
+pub struct GridChild {
+  /// The child element
+  pub element: Box<dyn UiElement>,
+
+  /// Placement of the child
+  pub cell: GridCell,
+}
+
+struct GridUserData {
+  /// Final response of each child and its position relative to the grid
+  children: Vec<(Response, Vec2)>,
+}
+
+/// A grid container element
+///
+/// Children are placed in cells by their column and row indices, and can span multiple columns and rows\
+/// Size of each column/row track can be:
+/// - `Absolute` - fixed size in pixels
+/// - `Relative` - ratio of the grid size (without the padding)
+/// - `Auto` - fit to the largest child placed in the track
+/// - `Remaining` - ratio of the space left over after all other tracks have been sized
+///
+/// If a child is placed outside of the specified tracks, `Auto` tracks are added as needed
+#[derive(Setters)]
+#[setters(no_std, prefix = "with_")]
+pub struct Grid {
+  /// Size of the grid
+  #[setters(into)]
+  pub size: Size2d,
+
+  /// Sizes of the column tracks
+  #[setters(into)]
+  pub columns: Vec<Size>,
+
+  /// Sizes of the row tracks
+  #[setters(into)]
+  pub rows: Vec<Size>,
+
+  /// Gap between the columns
+  pub column_gap: f32,
+
+  /// Gap between the rows
+  pub row_gap: f32,
+
+  /// Padding inside the grid (distance from the edges to the cells)
+  #[setters(into)]
+  pub padding: Sides<f32>,
+
+  /// Default alignment of the children within their cells
+  #[setters(into)]
+  pub align: Alignment2d,
+
+  #[setters(skip)]
+  pub background_frame: Box<dyn Frame>,
+
+  /// List of children elements
+  #[setters(skip)]
+  pub children: Vec<GridChild>,
+}
+
+impl Default for Grid {
+  fn default() -> Self {
+    Self {
+      size: (Size::Auto, Size::Auto).into(),
+      columns: Vec::new(),
+      rows: Vec::new(),
+      column_gap: 0.,
+      row_gap: 0.,
+      padding: Sides::all(0.),
+      align: Alignment2d::default(),
+      background_frame: Box::<RectFrame>::default(),
+      children: Vec::new(),
+    }
+  }
+}
+
+impl Grid {
+  /// Add children elements, placing them in consecutive cells, row by row
+  ///
+  /// Placement continues from the position based on the number of children already added
+  pub fn with_children(mut self, ui: impl FnOnce(&mut ElementList)) -> Self {
+    let column_count = self.columns.len().max(1);
+    for element in ElementList::from_callback(ui).0 {
+      let index = self.children.len();
+      self.children.push(GridChild {
+        element,
+        cell: GridCell::new(index % column_count, index / column_count),
+      });
+    }
+    self
+  }
+
+  /// Add a single child element at the specified cell
+  pub fn with_child(mut self, cell: GridCell, element: impl UiElement + 'static) -> Self {
+    self.children.push(GridChild {
+      element: Box::new(element),
+      cell,
+    });
+    self
+  }
+
+  /// Set both the column and row gap
+  pub fn with_gap(self, gap: f32) -> Self {
+    Self {
+      column_gap: gap,
+      row_gap: gap,
+      ..self
+    }
+  }
+
+  pub fn with_background(mut self, frame: impl Frame + 'static) -> Self {
+    self.background_frame = Box::new(frame);
+    self
+  }
+}
+
+/// Compute the sizes of the tracks on a single axis
+///
+/// `spans` is a list of (track range, measured size) pairs for each child
+fn compute_tracks(
+  tracks: &[Size],
+  count: usize,
+  spans: &[(core::ops::Range<usize>, f32)],
+  available: f32,
+  gap: f32,
+) -> Vec<f32> {
+  let track_size = |idx: usize| tracks.get(idx).copied().unwrap_or(Size::Auto);
+
+  //Fixed and relative tracks
+  let mut sizes: Vec<f32> = (0..count).map(|idx| match track_size(idx) {
+    Size::Absolute(size) => size,
+    Size::Relative(fraction) => available * fraction,
+    Size::Auto | Size::Remaining(_) => 0.,
+  }).collect();
+
+  //Auto tracks: first fit the children spanning a single track, then the ones spanning multiple
+  for (range, size) in spans.iter().filter(|(range, _)| range.len() == 1) {
+    if track_size(range.start) == Size::Auto {
+      sizes[range.start] = sizes[range.start].max(*size);
+    }
+  }
+  for (range, size) in spans.iter().filter(|(range, _)| range.len() > 1) {
+    let current = sizes[range.clone()].iter().sum::<f32>() + gap * (range.len() - 1) as f32;
+    let auto_tracks: Vec<usize> = range.clone().filter(|&idx| track_size(idx) == Size::Auto).collect();
+    if *size > current && !auto_tracks.is_empty() {
+      let extra = (size - current) / auto_tracks.len() as f32;
+      for idx in auto_tracks {
+        sizes[idx] += extra;
+      }
+    }
+  }
+
+  //Remaining tracks share the leftover space
+  let used = sizes.iter().sum::<f32>() + gap * count.saturating_sub(1) as f32;
+  let leftover = (available - used).max(0.);
+  for (idx, size) in sizes.iter_mut().enumerate() {
+    if let Size::Remaining(fraction) = track_size(idx) {
+      *size = leftover * fraction;
+    }
+  }
+
+  sizes
+}
+
+/// Get the offset of the track start, and the total size of the span
+fn span_geometry(sizes: &[f32], range: core::ops::Range<usize>, gap: f32) -> (f32, f32) {
+  let offset = sizes[..range.start].iter().map(|size| size + gap).sum();
+  let size = sizes[range.clone()].iter().sum::<f32>() + gap * (range.len() - 1) as f32;
+  (offset, size)
+}
+
+impl UiElement for Grid {
+  fn name(&self) -> &'static str {
+    "grid"
+  }
+
+  fn size(&self) -> Option<Size2d> {
+    Some(self.size)
+  }
+
+  fn measure(&self, ctx: MeasureContext) -> Response {
+    let inner_max_size = compute_size(ctx.layout, self.size, ctx.layout.max_size) - self.padding.sum_vec();
+    let child_measure = |element: &dyn UiElement, max_size: Vec2| {
+      element.measure(MeasureContext {
+        painter: ctx.painter,
+        layout: &LayoutInfo {
+          position: ctx.layout.position,
+          max_size,
+          direction: Direction::Horizontal,
+          remaining_space: None,
+        },
+        state: ctx.state,
+        current_font: ctx.current_font,
+      })
+    };
+
+    //Measure the children to fit the auto-sized tracks
+    let measures: Vec<Response> = self.children.iter()
+      .map(|child| child_measure(&*child.element, inner_max_size))
+      .collect();
+
+    let column_count = self.children.iter()
+      .map(|child| child.cell.columns().end)
+      .fold(self.columns.len(), usize::max);
+    let row_count = self.children.iter()
+      .map(|child| child.cell.rows().end)
+      .fold(self.rows.len(), usize::max);
+
+    let column_spans: Vec<_> = self.children.iter().zip(&measures)
+      .map(|(child, measure)| (child.cell.columns(), measure.size.x))
+      .collect();
+    let row_spans: Vec<_> = self.children.iter().zip(&measures)
+      .map(|(child, measure)| (child.cell.rows(), measure.size.y))
+      .collect();
+    let column_sizes = compute_tracks(&self.columns, column_count, &column_spans, inner_max_size.x, self.column_gap);
+    let row_sizes = compute_tracks(&self.rows, row_count, &row_spans, inner_max_size.y, self.row_gap);
+
+    let content_size = vec2(
+      column_sizes.iter().sum::<f32>() + self.column_gap * column_count.saturating_sub(1) as f32,
+      row_sizes.iter().sum::<f32>() + self.row_gap * row_count.saturating_sub(1) as f32,
+    );
+
+    //Place the children within their cells
+    //They are re-measured with the cell size as the maximum size, so that relative sizes are relative to the cell
+    let children = self.children.iter().map(|child| {
+      let (x, width) = span_geometry(&column_sizes, child.cell.columns(), self.column_gap);
+      let (y, height) = span_geometry(&row_sizes, child.cell.rows(), self.row_gap);
+      let cell_size = vec2(width, height);
+      let measure = child_measure(&*child.element, cell_size);
+      let align = child.cell.align.unwrap_or(self.align);
+      let offset = vec2(x, y) + align.offset(cell_size, measure.size);
+      (measure, self.padding.top_left() + offset)
+    }).collect();
+
+    Response {
+      size: compute_size(ctx.layout, self.size, content_size + self.padding.sum_vec()),
+      user_data: Some(Box::new(GridUserData { children })),
+      ..Default::default()
+    }
+  }
+
+  fn process(&self, ctx: ProcessContext) {
+    let user_data: &GridUserData = ctx.measure.user_data
+      .as_ref().expect("no user data attached to grid")
+      .downcast_ref().expect("invalid user data type");
+
+    self.background_frame.draw(ctx.paint_target, ctx.measure.rect(ctx.layout.position));
+
+    for (child, (measure, offset)) in self.children.iter().zip(&user_data.children) {
+      child.element.process(ProcessContext {
+        painter: ctx.painter,
+        measure,
+        layout: &LayoutInfo {
+          position: ctx.layout.position + *offset,
+          max_size: measure.size,
+          direction: Direction::Horizontal,
+          remaining_space: None,
+        },
+        paint_target: ctx.paint_target,
+        paint_overlay: ctx.paint_overlay,
+        state: ctx.state,
+        current_font: ctx.current_font,
+        input: ctx.input,
+        signal: ctx.signal,
+        time: ctx.time,
+        root_rect: ctx.root_rect,
+        overlay_occluders: ctx.overlay_occluders,
+      });
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_compute_tracks_fixed_and_relative() {
+    let tracks = [Size::Absolute(50.), Size::Relative(0.25)];
+    let sizes = compute_tracks(&tracks, 2, &[], 200., 0.);
+    assert_eq!(sizes, [50., 50.]);
+  }
+
+  #[test]
+  fn test_compute_tracks_auto_fits_children() {
+    let tracks = [Size::Auto, Size::Auto];
+    let spans = [(0..1, 30.), (0..1, 40.), (1..2, 10.)];
+    let sizes = compute_tracks(&tracks, 2, &spans, 200., 0.);
+    assert_eq!(sizes, [40., 10.]);
+  }
+
+  #[test]
+  fn test_compute_tracks_missing_tracks_are_auto() {
+    let sizes = compute_tracks(&[], 2, &[(1..2, 25.)], 200., 0.);
+    assert_eq!(sizes, [0., 25.]);
+  }
+
+  #[test]
+  fn test_compute_tracks_spanning_child() {
+    //The spanning child is wider than both tracks and the gap, the extra space goes to the auto track only
+    let tracks = [Size::Absolute(20.), Size::Auto];
+    let spans = [(1..2, 10.), (0..2, 50.)];
+    let sizes = compute_tracks(&tracks, 2, &spans, 200., 5.);
+    assert_eq!(sizes, [20., 25.]);
+  }
+
+  #[test]
+  fn test_compute_tracks_remaining() {
+    let tracks = [Size::Absolute(40.), Size::Remaining(0.5), Size::Remaining(0.5)];
+    let sizes = compute_tracks(&tracks, 3, &[], 100., 10.);
+    assert_eq!(sizes, [40., 20., 20.]);
+  }
+
+  #[test]
+  fn test_compute_tracks_remaining_no_space_left() {
+    let tracks = [Size::Absolute(150.), Size::Remaining(1.)];
+    let sizes = compute_tracks(&tracks, 2, &[], 100., 0.);
+    assert_eq!(sizes, [150., 0.]);
+  }
+}
@@ -1,13 +1,33 @@
 use derive_setters::Setters;
-use glam::{vec2, Affine2};
+use glam::{vec2, Affine2, Vec2};
 use hui_painter::{paint::command::{PaintRectangle, PaintTransform}, texture::TextureHandle};
 use crate::{
   element::{MeasureContext, ProcessContext, UiElement},
-  layout::{compute_size, Size, Size2d},
+  layout::{compute_size, Alignment, Alignment2d, Size, Size2d},
   measure::Response,
-  rect::{Corners, FillColor},
+  rect::{Corners, FillColor, Rect},
 };
 
+/// Controls how the image is fitted inside of the element
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ImageFit {
+  /// Stretch the image to fill the whole element (aspect ratio is not preserved)
+  #[default]
+  Fill,
+
+  /// Scale the image to fit inside of the element, preserving aspect ratio\
+  /// (may leave empty space on one of the axes)
+  Contain,
+
+  /// Scale the image to cover the whole element, preserving aspect ratio\
+  /// (parts of the image may get cropped)
+  Cover,
+
+  /// Draw the image at its original size\
+  /// (parts of the image may get cropped)
+  None,
+}
+
 #[derive(Setters)]
 #[setters(no_std, prefix = "with_")]
 pub struct Image {
@@ -24,6 +44,21 @@ pub struct Image {
   #[setters(into)]
   pub size: Size2d,
 
+  /// Controls how the image is fitted inside of the element
+  pub fit: ImageFit,
+
+  /// Alignment of the image inside of the element\
+  /// (only has effect if `fit` is not [`ImageFit::Fill`])
+  #[setters(into)]
+  pub align: Alignment2d,
+
+  /// Region of the texture to draw, in normalized (`0.0..=1.0`) texture coordinates
+  ///
+  /// Useful for drawing a single sprite out of a sprite sheet\
+  /// Use [`Image::with_uv_pixels`] to specify the region in pixels instead
+  #[setters(into)]
+  pub uv: Rect,
+
   /// Color of the image
   ///
   /// Image will get multiplied/tinted by this color or gradient
@@ -43,10 +78,26 @@ impl Image {
         width: Size::Auto,
         height: Size::Auto,
       },
+      fit: ImageFit::Fill,
+      align: Alignment2d::all(Alignment::Center),
+      uv: Rect::UNIT,
       color: (1., 1., 1.).into(),
       corner_radius: Corners::all(0.),
     }
   }
+
+  /// Set the region of the texture to draw, in pixels
+  pub fn with_uv_pixels(mut self, rect: impl Into<Rect>) -> Self {
+    let rect: Rect = rect.into();
+    let dim = self.image.size().as_vec2().max(Vec2::ONE);
+    self.uv = Rect::new(rect.position / dim, rect.size / dim);
+    self
+  }
+
+  /// Size of the drawn texture region, in pixels
+  fn source_size(&self) -> Vec2 {
+    (self.image.size().as_vec2() * self.uv.size).max(Vec2::splat(f32::EPSILON))
+  }
 }
 
 impl UiElement for Image {
@@ -59,17 +110,17 @@ impl UiElement for Image {
   }
 
   fn measure(&self, ctx: MeasureContext) -> Response {
-    let dim = self.image.size();
-    let pre_size = compute_size(ctx.layout, self.size, dim.as_vec2());
+    let dim = self.source_size();
+    let pre_size = compute_size(ctx.layout, self.size, dim);
     Response {
       size: compute_size(ctx.layout, self.size, vec2(
         match self.size.height {
-          Size::Auto => dim.x as f32,
-          _ => (pre_size.y / dim.y as f32) * dim.x as f32,
+          Size::Auto => dim.x,
+          _ => (pre_size.y / dim.y) * dim.x,
         },
-        match self.size.height {
-          Size::Auto => dim.x as f32,
-          _ => (pre_size.y / dim.y as f32) * dim.x as f32,
+        match self.size.width {
+          Size::Auto => dim.y,
+          _ => (pre_size.x / dim.x) * dim.y,
         },
       )),
       ..Default::default()
@@ -77,18 +128,50 @@ impl UiElement for Image {
   }
 
   fn process(&self, ctx: ProcessContext) {
-    if !self.color.is_transparent() {
-      ctx.paint_target.add(
-        PaintTransform {
-          transform: Affine2::from_translation(ctx.layout.position),
-          child: PaintRectangle {
-            size: ctx.measure.size,
-            color: self.color,
-            texture: Some(self.image),
-            ..Default::default()
-          },
-        }
-      );
+    if self.color.is_transparent() {
+      return
+    }
+
+    let available = ctx.measure.size;
+    let source = self.source_size();
+
+    //Size of the whole (uncropped) image, as it would be drawn
+    let draw_size = match self.fit {
+      ImageFit::Fill => available,
+      ImageFit::Contain => source * (available / source).min_element(),
+      ImageFit::Cover => source * (available / source).max_element(),
+      ImageFit::None => source,
+    };
+    let offset = self.align.offset(available, draw_size);
+
+    //Crop the parts of the image that don't fit inside of the element
+    let visible_min = offset.max(Vec2::ZERO);
+    let visible_max = (offset + draw_size).min(available);
+    if visible_max.cmple(visible_min).any() {
+      return
     }
+
+    //Map the visible part back to texture coordinates
+    let uv_min = self.uv.position + (visible_min - offset) / draw_size * self.uv.size;
+    let uv_max = self.uv.position + (visible_max - offset) / draw_size * self.uv.size;
+
+    ctx.paint_target.add(
+      PaintTransform {
+        transform: Affine2::from_translation(ctx.layout.position + visible_min),
+        child: PaintRectangle {
+          size: visible_max - visible_min,
+          color: self.color,
+          texture: Some(self.image),
+          texture_uv: Corners {
+            top_left: uv_min,
+            top_right: vec2(uv_max.x, uv_min.y),
+            bottom_left: vec2(uv_min.x, uv_max.y),
+            bottom_right: uv_max,
+          },
+          border_radius: self.corner_radius,
+          ..Default::default()
+        },
+      }
+    );
   }
 }
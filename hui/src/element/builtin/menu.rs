@@ -0,0 +1,640 @@
+//! menu bar and context menu elements with nested submenus
+
+use core::hash::Hash;
+use alloc::{borrow::Cow, boxed::Box, vec, vec::Vec};
+use derive_setters::Setters;
+use glam::{vec2, Affine2, Vec2, Vec4};
+use hui_painter::{
+  paint::command::{text::PaintText, PaintCommand, PaintList, PaintTransform},
+  text::FontHandle,
+  PainterInstance,
+};
+use crate::{
+  element::{MeasureContext, ProcessContext, UiElement},
+  frame::{Frame, RectFrame},
+  input::{KeyboardKey, MouseButton},
+  layout::{compute_size, Size, Size2d},
+  measure::Response,
+  rect::{Rect, Sides},
+  signal::{trigger::SignalTrigger, Signal},
+  state::{state_id, State, StateId},
+};
+
+/// State of a [`MenuBar`] or a [`ContextMenu`]
+///
+/// Stored in the global scope of the [`StateRepo`](crate::state::StateRepo),
+/// as the popups are not a part of the element hierarchy
+#[derive(Clone, Debug, Default)]
+pub struct MenuState {
+  /// Whether the menu is currently open
+  pub open: bool,
+
+  /// Indices of the items with currently open submenus, starting from the top level
+  ///
+  /// For a [`MenuBar`], the first index is the index of the open menu
+  pub path: Vec<usize>,
+
+  /// Position the context menu was opened at
+  pub position: Vec2,
+}
+
+impl State for MenuState {}
+
+/// A single item of a menu
+pub struct MenuItem {
+  /// Label of the item
+  pub label: Cow<'static, str>,
+
+  /// Shortcut label displayed on the right side of the item\
+  /// This is purely informational, the shortcut itself has to be handled separately
+  pub shortcut: Option<Cow<'static, str>>,
+
+  /// Disabled items are grayed out and can't be interacted with
+  pub enabled: bool,
+
+  /// Whether this item is a separator line (in which case all other fields are ignored)
+  pub separator: bool,
+
+  /// Items of the submenu\
+  /// If not empty, hovering the item opens the submenu instead of firing the signal
+  pub submenu: Vec<MenuItem>,
+
+  /// Signal that will be fired when the item gets clicked
+  pub on_click: Option<SignalTrigger>,
+}
+
+impl MenuItem {
+  pub fn new(label: impl Into<Cow<'static, str>>) -> Self {
+    Self {
+      label: label.into(),
+      shortcut: None,
+      enabled: true,
+      separator: false,
+      submenu: Vec::new(),
+      on_click: None,
+    }
+  }
+
+  /// Create a separator line
+  pub fn separator() -> Self {
+    Self {
+      separator: true,
+      ..Self::new("")
+    }
+  }
+
+  /// Create an item that opens a submenu
+  pub fn submenu(label: impl Into<Cow<'static, str>>, items: impl IntoIterator<Item = MenuItem>) -> Self {
+    Self {
+      submenu: items.into_iter().collect(),
+      ..Self::new(label)
+    }
+  }
+
+  pub fn with_shortcut(self, shortcut: impl Into<Cow<'static, str>>) -> Self {
+    Self {
+      shortcut: Some(shortcut.into()),
+      ..self
+    }
+  }
+
+  pub fn with_enabled(self, enabled: bool) -> Self {
+    Self { enabled, ..self }
+  }
+
+  pub fn on_click<S: Signal, T: Fn() -> S + 'static>(self, f: T) -> Self {
+    Self {
+      on_click: Some(SignalTrigger::new(f)),
+      ..self
+    }
+  }
+
+  fn has_submenu(&self) -> bool {
+    !self.submenu.is_empty()
+  }
+}
+
+/// Appearance of the menu popups, shared by [`MenuBar`] and [`ContextMenu`]
+#[derive(Setters)]
+#[setters(no_std, prefix = "with_")]
+pub struct MenuStyle {
+  /// Padding inside each of the items
+  #[setters(into)]
+  pub padding: Sides<f32>,
+
+  /// Minimum gap between the label and the shortcut label (or the submenu arrow)
+  pub shortcut_gap: f32,
+
+  /// Minimum width of the popups, in pixels
+  pub min_width: f32,
+
+  /// Height of the separators, in pixels
+  pub separator_height: f32,
+
+  /// Color of the labels
+  #[setters(into)]
+  pub color: Vec4,
+
+  /// Color of the shortcut labels and submenu arrows
+  #[setters(into)]
+  pub shortcut_color: Vec4,
+
+  /// Color of the labels of disabled items
+  #[setters(into)]
+  pub disabled_color: Vec4,
+
+  /// Font to use for rendering the labels\
+  /// If set to `None` either currently selected font or the default font will be used
+  #[setters(into)]
+  pub font: Option<FontHandle>,
+
+  /// Size of the labels, in points (these are not pixels)
+  pub text_size: f32,
+
+  /// Background frame of the popups
+  #[setters(skip)]
+  pub popup: Box<dyn Frame>,
+
+  /// Frame drawn behind the item under the mouse pointer (or the item with an open submenu)
+  #[setters(skip)]
+  pub item_hovered: Box<dyn Frame>,
+
+  /// Frame of the separator line (drawn 1 pixel high, in the middle of the separator)
+  #[setters(skip)]
+  pub separator: Box<dyn Frame>,
+}
+
+impl Default for MenuStyle {
+  fn default() -> Self {
+    Self {
+      padding: Sides::horizontal_vertical(10., 4.),
+      shortcut_gap: 30.,
+      min_width: 120.,
+      separator_height: 9.,
+      color: Vec4::new(1., 1., 1., 1.),
+      shortcut_color: Vec4::new(1., 1., 1., 0.6),
+      disabled_color: Vec4::new(1., 1., 1., 0.35),
+      font: None,
+      text_size: 16.,
+      popup: Box::new(RectFrame::color((0.2, 0.2, 0.2))),
+      item_hovered: Box::new(RectFrame::color((0.0, 0.0, 0.75))),
+      separator: Box::new(RectFrame::color((0.35, 0.35, 0.35))),
+    }
+  }
+}
+
+impl MenuStyle {
+  /// Text displayed in place of the shortcut label for items with submenus
+  const SUBMENU_ARROW: &'static str = ">";
+
+  pub fn with_popup(mut self, frame: impl Frame + 'static) -> Self {
+    self.popup = Box::new(frame);
+    self
+  }
+
+  pub fn with_item_hovered(mut self, frame: impl Frame + 'static) -> Self {
+    self.item_hovered = Box::new(frame);
+    self
+  }
+
+  pub fn with_separator(mut self, frame: impl Frame + 'static) -> Self {
+    self.separator = Box::new(frame);
+    self
+  }
+
+  fn paint_text(&self, text: Cow<'static, str>, color: Vec4, current_font: FontHandle) -> PaintText {
+    PaintText::new(text, self.font.unwrap_or(current_font), self.text_size, color)
+  }
+
+  fn text_width(&self, text: Cow<'static, str>, painter: &PainterInstance, current_font: FontHandle) -> f32 {
+    self.paint_text(text, self.color, current_font).bounds(painter).size.x
+  }
+
+  fn line_height(&self, painter: &PainterInstance, current_font: FontHandle) -> f32 {
+    self.paint_text("".into(), self.color, current_font).line_height(painter)
+  }
+
+  /// Draw the text at the given position
+  fn draw_text(&self, target: &mut PaintList, text: Cow<'static, str>, color: Vec4, position: Vec2, current_font: FontHandle) {
+    if text.is_empty() || color.w == 0. {
+      return
+    }
+    target.add(PaintTransform {
+      transform: Affine2::from_translation(position),
+      child: self.paint_text(text, color, current_font),
+    });
+  }
+
+  /// Right-aligned text of the item (shortcut label or submenu arrow)
+  fn right_text(item: &MenuItem) -> Option<Cow<'static, str>> {
+    match item.has_submenu() {
+      true => Some(Self::SUBMENU_ARROW.into()),
+      false => item.shortcut.clone(),
+    }
+  }
+
+  /// Process a popup with the given items, along with all of its open submenus
+  ///
+  /// `depth` is the index of the popup's entry within `path`\
+  /// `hovered` is set if the mouse pointer is over any of the popups\
+  /// Returns `true` if any item was activated
+  fn process_popup(
+    &self,
+    items: &[MenuItem],
+    position: Vec2,
+    depth: usize,
+    path: &mut Vec<usize>,
+    ctx: &mut ProcessContext,
+    hovered: &mut bool,
+  ) -> bool {
+    let line_height = self.line_height(ctx.painter, ctx.current_font);
+    let item_height = line_height + self.padding.sum_vertical();
+    let text_offset = ((item_height - line_height) / 2.).round();
+
+    //Compute the popup size, with the right column wide enough to fit all shortcut labels and arrows
+    let label_width = items.iter()
+      .filter(|item| !item.separator)
+      .map(|item| self.text_width(item.label.clone(), ctx.painter, ctx.current_font))
+      .fold(0., f32::max);
+    let right_width = items.iter()
+      .filter(|item| !item.separator)
+      .filter_map(Self::right_text)
+      .map(|text| self.text_width(text, ctx.painter, ctx.current_font))
+      .fold(None, |acc: Option<f32>, width| Some(acc.unwrap_or(0.).max(width)));
+    let width = (label_width + right_width.map_or(0., |width| width + self.shortcut_gap) + self.padding.sum_horizontal())
+      .max(self.min_width);
+    let height = items.iter()
+      .map(|item| if item.separator { self.separator_height } else { item_height })
+      .sum();
+
+    //Keep the popup within the root rect
+    let root = ctx.root_rect;
+    let size = vec2(width, height);
+    let popup_rect = Rect::new(
+      position.min(root.position + root.size - size).max(root.position),
+      size,
+    );
+    self.popup.draw(ctx.paint_overlay, popup_rect);
+
+    //The popup blocks the input to the elements below it
+    let input = ctx.input.overlay();
+    ctx.overlay_occluders.push(popup_rect);
+    *hovered |= input.check_hover(popup_rect);
+
+    let mut activated = false;
+    let mut open_submenu = None;
+    let mut y = 0.;
+    for (idx, item) in items.iter().enumerate() {
+      if item.separator {
+        self.separator.draw(ctx.paint_overlay, Rect::new(
+          popup_rect.position + vec2(self.padding.left, y + ((self.separator_height - 1.) / 2.).round()),
+          vec2(width - self.padding.sum_horizontal(), 1.),
+        ));
+        y += self.separator_height;
+        continue
+      }
+
+      let item_rect = Rect::new(popup_rect.position + vec2(0., y), vec2(width, item_height));
+      y += item_height;
+
+      //Hovering an item opens its submenu and closes any other submenus at this level
+      if item.enabled {
+        if input.check_hover(item_rect) {
+          path.truncate(depth);
+          if item.has_submenu() {
+            path.push(idx);
+          }
+        }
+        if !item.has_submenu() && input.check_click(item_rect).is_some() {
+          if let Some(signal) = &item.on_click {
+            signal.fire(ctx.signal);
+          }
+          activated = true;
+        }
+      }
+      let submenu_open = item.has_submenu() && path.get(depth) == Some(&idx);
+      if submenu_open {
+        open_submenu = Some((item, item_rect));
+      }
+
+      //Draw the item
+      if item.enabled && (submenu_open || input.check_hover(item_rect)) {
+        self.item_hovered.draw(ctx.paint_overlay, item_rect);
+      }
+      let (color, right_color) = match item.enabled {
+        true => (self.color, self.shortcut_color),
+        false => (self.disabled_color, self.disabled_color),
+      };
+      self.draw_text(ctx.paint_overlay, item.label.clone(), color, item_rect.position + vec2(self.padding.left, text_offset), ctx.current_font);
+      if let Some(text) = Self::right_text(item) {
+        let x = width - self.padding.right - self.text_width(text.clone(), ctx.painter, ctx.current_font);
+        self.draw_text(ctx.paint_overlay, text, right_color, item_rect.position + vec2(x, text_offset), ctx.current_font);
+      }
+    }
+
+    //Submenus are placed to the right of their items, and drawn on top of the parent popup
+    if let Some((item, item_rect)) = open_submenu {
+      activated |= self.process_popup(
+        &item.submenu,
+        item_rect.position + vec2(item_rect.size.x, 0.),
+        depth + 1,
+        path,
+        ctx,
+        hovered,
+      );
+    }
+
+    activated
+  }
+
+  /// Check if the open menu should be closed because of a click outside of it, or the Escape key
+  fn should_close(&self, ctx: &ProcessContext, hovered: bool) -> bool {
+    let input = ctx.input.overlay();
+    let outside_click = !hovered && (
+      input.mouse_button_just_pressed(MouseButton::Primary) ||
+      input.mouse_button_just_pressed(MouseButton::Secondary)
+    );
+    outside_click || input.keys_pressed().any(|key| key == KeyboardKey::Escape)
+  }
+}
+
+/// A horizontal bar with a list of menus (e.g. File, Edit, View)
+///
+/// Clicking a menu title opens its popup, which is drawn on top of everything else rendered on the same layer\
+/// While a menu is open, hovering over other titles switches to their menus
+///
+/// Each top-level item is displayed as a title, and its submenu items make up the popup\
+/// (top-level items without submenus fire their signal when clicked)\
+/// Open popups block the input to the elements below them
+#[derive(Setters)]
+#[setters(no_std, prefix = "with_")]
+pub struct MenuBar {
+  /// Id used to store the state of the element
+  #[setters(skip)]
+  pub id: StateId,
+
+  /// Menus displayed in the bar
+  #[setters(skip)]
+  pub menus: Vec<MenuItem>,
+
+  /// Size of the element
+  #[setters(into)]
+  pub size: Size2d,
+
+  /// Padding inside each of the menu titles
+  #[setters(into)]
+  pub title_padding: Sides<f32>,
+
+  /// Appearance of the titles and popups
+  pub style: MenuStyle,
+
+  /// Background frame of the bar
+  #[setters(skip)]
+  pub frame: Box<dyn Frame>,
+
+  /// Frame of the menu title under the mouse pointer
+  #[setters(skip)]
+  pub title_hovered: Box<dyn Frame>,
+
+  /// Frame of the title of the currently open menu
+  #[setters(skip)]
+  pub title_open: Box<dyn Frame>,
+}
+
+impl MenuBar {
+  pub fn new(id: impl Hash) -> Self {
+    Self {
+      id: state_id(id),
+      menus: Vec::new(),
+      size: (Size::Relative(1.), Size::Auto).into(),
+      title_padding: Sides::horizontal_vertical(8., 4.),
+      style: MenuStyle::default(),
+      frame: Box::new(RectFrame::color((0.15, 0.15, 0.15))),
+      title_hovered: Box::new(RectFrame::color((0.25, 0.25, 0.25))),
+      title_open: Box::new(RectFrame::color((0.2, 0.2, 0.2))),
+    }
+  }
+
+  /// Add a menu to the bar
+  pub fn with_menu(mut self, label: impl Into<Cow<'static, str>>, items: impl IntoIterator<Item = MenuItem>) -> Self {
+    self.menus.push(MenuItem::submenu(label, items));
+    self
+  }
+
+  /// Add multiple menus to the bar
+  pub fn with_menus(mut self, menus: impl IntoIterator<Item = MenuItem>) -> Self {
+    self.menus.extend(menus);
+    self
+  }
+
+  pub fn with_frame(mut self, frame: impl Frame + 'static) -> Self {
+    self.frame = Box::new(frame);
+    self
+  }
+
+  pub fn with_title_hovered(mut self, frame: impl Frame + 'static) -> Self {
+    self.title_hovered = Box::new(frame);
+    self
+  }
+
+  pub fn with_title_open(mut self, frame: impl Frame + 'static) -> Self {
+    self.title_open = Box::new(frame);
+    self
+  }
+
+  fn title_widths(&self, painter: &PainterInstance, current_font: FontHandle) -> Vec<f32> {
+    self.menus.iter().map(|menu| {
+      self.style.text_width(menu.label.clone(), painter, current_font) + self.title_padding.sum_horizontal()
+    }).collect()
+  }
+}
+
+impl UiElement for MenuBar {
+  fn name(&self) -> &'static str {
+    "menu_bar"
+  }
+
+  fn size(&self) -> Option<Size2d> {
+    Some(self.size)
+  }
+
+  fn measure(&self, ctx: MeasureContext) -> Response {
+    Response {
+      size: compute_size(ctx.layout, self.size, vec2(
+        self.title_widths(ctx.painter, ctx.current_font).iter().sum(),
+        self.style.line_height(ctx.painter, ctx.current_font) + self.title_padding.sum_vertical(),
+      )),
+      ..Default::default()
+    }
+  }
+
+  fn process(&self, mut ctx: ProcessContext) {
+    let rect = ctx.measure.rect(ctx.layout.position);
+    let line_height = self.style.line_height(ctx.painter, ctx.current_font);
+    let text_offset = vec2(self.title_padding.left, ((rect.size.y - line_height) / 2.).round());
+
+    //The popups are not a part of the element hierarchy, so the state is stored in the global scope
+    let mut state = ctx.state.global(|state| {
+      state.acquire_or_default::<MenuState>(self.id).clone()
+    });
+
+    self.frame.draw(ctx.paint_target, rect);
+
+    //Menu titles
+    let mut x = 0.;
+    let mut title_rects = Vec::with_capacity(self.menus.len());
+    for (idx, (menu, width)) in self.menus.iter().zip(self.title_widths(ctx.painter, ctx.current_font)).enumerate() {
+      let title_rect = Rect::new(rect.position + vec2(x, 0.), vec2(width, rect.size.y));
+      title_rects.push(title_rect);
+      x += width;
+
+      let is_open = state.open && state.path.first() == Some(&idx);
+      if menu.enabled {
+        if ctx.input.mouse_button_just_pressed(MouseButton::Primary) && ctx.input.check_hover(title_rect) {
+          if is_open {
+            state.open = false;
+          } else if menu.has_submenu() {
+            state.open = true;
+            state.path = vec![idx];
+          }
+        } else if state.open && !is_open && menu.has_submenu() && ctx.input.check_hover(title_rect) {
+          state.path = vec![idx];
+        }
+        if !menu.has_submenu() && ctx.input.check_click(title_rect).is_some() {
+          if let Some(signal) = &menu.on_click {
+            signal.fire(ctx.signal);
+          }
+        }
+      }
+
+      let is_open = state.open && state.path.first() == Some(&idx);
+      if is_open {
+        self.title_open.draw(ctx.paint_target, title_rect);
+      } else if menu.enabled && ctx.input.check_hover(title_rect) {
+        self.title_hovered.draw(ctx.paint_target, title_rect);
+      }
+      let color = if menu.enabled { self.style.color } else { self.style.disabled_color };
+      self.style.draw_text(ctx.paint_target, menu.label.clone(), color, title_rect.position + text_offset, ctx.current_font);
+    }
+
+    //Popup of the open menu
+    if state.open {
+      match state.path.first().copied().filter(|&idx| idx < self.menus.len()) {
+        Some(idx) => {
+          let mut hovered = ctx.input.check_hover(rect);
+          let title_rect = title_rects[idx];
+          let activated = self.style.process_popup(
+            &self.menus[idx].submenu,
+            title_rect.position + vec2(0., title_rect.size.y),
+            1,
+            &mut state.path,
+            &mut ctx,
+            &mut hovered,
+          );
+          if activated || self.style.should_close(&ctx, hovered) {
+            state.open = false;
+          }
+        },
+        None => state.open = false,
+      }
+    }
+    if !state.open {
+      state.path.clear();
+    }
+
+    ctx.state.global(|repo| {
+      *repo.acquire_mut_or_default::<MenuState>(self.id) = state;
+    });
+  }
+}
+
+/// Wrapper that opens a context menu when the element gets right-clicked
+///
+/// The menu is opened at the mouse pointer position, and drawn on top of everything else rendered on the same layer\
+/// It gets closed once an item is activated, or the user clicks anywhere outside of it\
+/// Open popups block the input to the elements below them
+pub struct ContextMenu {
+  /// Id used to store the state of the wrapper
+  pub id: StateId,
+
+  /// The wrapped element
+  pub element: Box<dyn UiElement>,
+
+  /// Items of the menu
+  pub items: Vec<MenuItem>,
+
+  /// Appearance of the popups
+  pub style: MenuStyle,
+}
+
+impl ContextMenu {
+  pub fn new(id: impl Hash, element: Box<dyn UiElement>, items: impl IntoIterator<Item = MenuItem>) -> Self {
+    Self {
+      id: state_id(id),
+      element,
+      items: items.into_iter().collect(),
+      style: MenuStyle::default(),
+    }
+  }
+
+  pub fn with_style(self, style: MenuStyle) -> Self {
+    Self { style, ..self }
+  }
+}
+
+impl UiElement for ContextMenu {
+  fn name(&self) -> &'static str {
+    "context_menu"
+  }
+
+  fn size(&self) -> Option<Size2d> {
+    self.element.size()
+  }
+
+  fn measure(&self, ctx: MeasureContext) -> Response {
+    self.element.measure(ctx)
+  }
+
+  fn process(&self, mut ctx: ProcessContext) {
+    let rect = ctx.measure.rect(ctx.layout.position);
+
+    //The popups are not a part of the element hierarchy, so the state is stored in the global scope
+    let mut state = ctx.state.global(|state| {
+      state.acquire_or_default::<MenuState>(self.id).clone()
+    });
+
+    if state.open {
+      let mut hovered = false;
+      let activated = self.style.process_popup(&self.items, state.position, 0, &mut state.path, &mut ctx, &mut hovered);
+      if activated || self.style.should_close(&ctx, hovered) {
+        state.open = false;
+        state.path.clear();
+      }
+    }
+
+    //Open the menu on right click (the menu is opened on release, so it's not closed by the same click)
+    if ctx.input.check_click_with_button(rect, MouseButton::Secondary).is_some() {
+      state.open = true;
+      state.path.clear();
+      state.position = ctx.input.mouse_position();
+    }
+
+    ctx.state.global(|repo| {
+      *repo.acquire_mut_or_default::<MenuState>(self.id) = state;
+    });
+
+    self.element.process(ctx);
+  }
+}
+
+/// Extension trait for [`UiElement`] that adds the [`ElementContextMenuExt::with_context_menu`] method
+pub trait ElementContextMenuExt: UiElement {
+  /// Wrap the element in a [`ContextMenu`] that opens the given menu when the element gets right-clicked
+  fn with_context_menu(self, id: impl Hash, items: impl IntoIterator<Item = MenuItem>) -> ContextMenu;
+}
+
+impl<T: UiElement + 'static> ElementContextMenuExt for T {
+  fn with_context_menu(self, id: impl Hash, items: impl IntoIterator<Item = MenuItem>) -> ContextMenu {
+    ContextMenu::new(id, Box::new(self), items)
+  }
+}
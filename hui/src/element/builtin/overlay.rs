@@ -0,0 +1,137 @@
+//! a container element that stacks its children on top of each other
+
+use alloc::{boxed::Box, vec::Vec};
+use derive_setters::Setters;
+use glam::Vec2;
+use crate::{
+  element::{ElementList, MeasureContext, ProcessContext, UiElement},
+  layout::{compute_size, Alignment2d, LayoutInfo, Size, Size2d},
+  measure::Response,
+};
+
+/// A single child of an [`Overlay`] element
+pub struct OverlayChild {
+  /// The child element
+  pub element: Box<dyn UiElement>,
+
+  /// Alignment of the child within the overlay\
+  /// If set to `None`, the default alignment of the [`Overlay`] will be used
+  pub align: Option<Alignment2d>,
+}
+
+/// A container element that stacks its children on top of each other
+///
+/// All children are measured against the same layout, and the element is sized to fit the largest one\
+/// Each child is then placed according to its own alignment, and drawn in the order they were added
+#[derive(Setters)]
+#[setters(no_std, prefix = "with_")]
+pub struct Overlay {
+  /// Size of the overlay
+  #[setters(into)]
+  pub size: Size2d,
+
+  /// Default alignment of the children elements
+  #[setters(into)]
+  pub align: Alignment2d,
+
+  /// List of children elements
+  #[setters(skip)]
+  pub children: Vec<OverlayChild>,
+}
+
+impl Default for Overlay {
+  fn default() -> Self {
+    Self {
+      size: (Size::Auto, Size::Auto).into(),
+      align: Alignment2d::default(),
+      children: Vec::new(),
+    }
+  }
+}
+
+impl Overlay {
+  /// Add children elements, placed according to the default alignment
+  pub fn with_children(mut self, ui: impl FnOnce(&mut ElementList)) -> Self {
+    self.children.extend(ElementList::from_callback(ui).0.into_iter().map(|element| {
+      OverlayChild { element, align: None }
+    }));
+    self
+  }
+
+  /// Add a single child element with a custom alignment
+  pub fn with_child(mut self, align: impl Into<Alignment2d>, element: impl UiElement + 'static) -> Self {
+    self.children.push(OverlayChild {
+      element: Box::new(element),
+      align: Some(align.into()),
+    });
+    self
+  }
+
+  /// Get the layout all of the children are measured against
+  fn child_layout(&self, layout: &LayoutInfo) -> LayoutInfo {
+    LayoutInfo {
+      position: layout.position,
+      max_size: compute_size(layout, self.size, layout.max_size),
+      direction: layout.direction,
+      remaining_space: None,
+    }
+  }
+}
+
+impl UiElement for Overlay {
+  fn name(&self) -> &'static str {
+    "overlay"
+  }
+
+  fn size(&self) -> Option<Size2d> {
+    Some(self.size)
+  }
+
+  fn measure(&self, ctx: MeasureContext) -> Response {
+    let child_layout = self.child_layout(ctx.layout);
+    let measures: Vec<Response> = self.children.iter().map(|child| {
+      child.element.measure(MeasureContext {
+        painter: ctx.painter,
+        layout: &child_layout,
+        state: ctx.state,
+        current_font: ctx.current_font,
+      })
+    }).collect();
+    let content_size = measures.iter()
+      .fold(Vec2::ZERO, |size, measure| size.max(measure.size));
+    Response {
+      size: compute_size(ctx.layout, self.size, content_size),
+      user_data: Some(Box::new(measures)),
+      ..Default::default()
+    }
+  }
+
+  fn process(&self, ctx: ProcessContext) {
+    let measures: &Vec<Response> = ctx.measure.user_data
+      .as_ref().expect("no user data attached to overlay")
+      .downcast_ref().expect("invalid user data type");
+
+    let child_layout = self.child_layout(ctx.layout);
+    for (child, measure) in self.children.iter().zip(measures) {
+      let align = child.align.unwrap_or(self.align);
+      let offset = align.offset(ctx.measure.size, measure.size);
+      child.element.process(ProcessContext {
+        painter: ctx.painter,
+        measure,
+        layout: &LayoutInfo {
+          position: ctx.layout.position + offset,
+          ..child_layout
+        },
+        paint_target: ctx.paint_target,
+        paint_overlay: ctx.paint_overlay,
+        state: ctx.state,
+        current_font: ctx.current_font,
+        input: ctx.input,
+        signal: ctx.signal,
+        time: ctx.time,
+        root_rect: ctx.root_rect,
+        overlay_occluders: ctx.overlay_occluders,
+      });
+    }
+  }
+}
@@ -1,14 +1,24 @@
 use alloc::boxed::Box;
 use derive_setters::Setters;
 use glam::vec2;
+use hui_painter::paint::command::{PaintClip, PaintList};
 use crate::{
   element::{MeasureContext, ProcessContext, UiElement},
   frame::{Frame, RectFrame},
   layout::{compute_size, Size, Size2d},
   measure::Response,
+  rect::Rect,
 };
 
-//TODO: Use Frames here instead of FillColor
+/// Direction in which the [`ProgressBar`] fills up
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum FillDirection {
+  #[default]
+  LeftToRight,
+  RightToLeft,
+  TopToBottom,
+  BottomToTop,
+}
 
 #[derive(Setters)]
 #[setters(no_std, prefix = "with_")]
@@ -20,11 +30,25 @@ pub struct ProgressBar {
   #[setters(into)]
   pub size: Size2d,
 
-  /// Foreground (bar) color
+  /// Direction in which the bar fills up
+  pub direction: FillDirection,
+
+  /// If set to true, the value is ignored and an animated segment sweeps across the bar instead
+  pub indeterminate: bool,
+
+  /// Length of the moving segment in indeterminate mode, as a fraction of the bar length
+  pub indeterminate_length: f32,
+
+  /// Speed of the moving segment in indeterminate mode, in sweeps per second
+  pub indeterminate_speed: f32,
+
+  /// Foreground (bar) frame
+  ///
+  /// Always drawn at the full size of the element, and clipped to the filled part
   #[setters(skip)]
   pub foreground: Box<dyn Frame>,
 
-  /// Background color
+  /// Background frame
   #[setters(skip)]
   pub background: Box<dyn Frame>,
 }
@@ -41,6 +65,17 @@ impl ProgressBar {
     self.foreground = Box::new(frame);
     self
   }
+
+  /// Get the filled part of the bar (`start..end`, as fractions of the bar length) for the current frame
+  fn fill_range(&self, elapsed: f32) -> (f32, f32) {
+    if self.indeterminate {
+      let length = self.indeterminate_length.clamp(0., 1.);
+      let start = (elapsed * self.indeterminate_speed).fract() * (1. + length) - length;
+      (start.max(0.), (start + length).min(1.))
+    } else {
+      (0., self.value.clamp(0., 1.))
+    }
+  }
 }
 
 impl Default for ProgressBar {
@@ -48,6 +83,10 @@ impl Default for ProgressBar {
     Self {
       value: 0.,
       size: Size::Auto.into(),
+      direction: FillDirection::LeftToRight,
+      indeterminate: false,
+      indeterminate_length: 0.3,
+      indeterminate_speed: 0.75,
       foreground: Box::new(RectFrame::color((0.0, 0.0, 1.0, 1.0))),
       background: Box::new(RectFrame::color((0.0, 0.0, 0.0, 1.0))),
     }
@@ -72,50 +111,50 @@ impl UiElement for ProgressBar {
   }
 
   fn process(&self, ctx: ProcessContext) {
-    let value = self.value.clamp(0., 1.);
+    let rect = Rect::new(ctx.layout.position, ctx.measure.size);
+    let (start, end) = self.fill_range(ctx.time.elapsed);
+    let filled = (end - start).max(0.);
 
     //FIXME: these optimizations may not be valid
-    if value < 1. || !self.foreground.covers_opaque() {
-      self.background.draw(ctx.paint_target, (ctx.layout.position, ctx.measure.size).into());
+    if filled < 1. || !self.foreground.covers_opaque() {
+      self.background.draw(ctx.paint_target, rect);
     }
-    if value > 0. {
-      self.foreground.draw(ctx.paint_target, (ctx.layout.position, ctx.measure.size * vec2(value, 1.)).into());
+    if filled <= 0. {
+      return
     }
 
-    // let rounded_corners =
-    //   (self.corner_radius.max_f32() > 0.).then_some({
-    //     //HACK: fix clipping issues; //todo: get rid of this
-    //     let mut radii = self.corner_radius;
-    //     let width = ctx.measure.size.x * value;
-    //     if width <= radii.max_f32() * 2. {
-    //       radii.bottom_right = 0.;
-    //       radii.top_right = 0.;
-    //     }
-    //     if width <= radii.max_f32() {
-    //       radii.bottom_left = 0.;
-    //       radii.top_left = 0.;
-    //     }
-    //     RoundedCorners::from_radius(radii)
-    //   });
-    // if value < 1. {
-    //   ctx.draw.add(UiDrawCommand::Rectangle {
-    //     position: ctx.layout.position,
-    //     size: ctx.measure.size,
-    //     color: self.background.corners(),
-    //     texture: None,
-    //     texture_uv: None,
-    //     rounded_corners
-    //   });
-    // }
-    // if value > 0. {
-    //   ctx.draw.add(UiDrawCommand::Rectangle {
-    //     position: ctx.layout.position,
-    //     size: ctx.measure.size * vec2(value, 1.0),
-    //     color: self.foreground.corners(),
-    //     texture: None,
-    //     texture_uv: None,
-    //     rounded_corners,
-    //   });
-    // }
+    //Compute the filled part of the bar, depending on the fill direction
+    let size = rect.size;
+    let fill_rect = match self.direction {
+      FillDirection::LeftToRight => Rect::new(
+        rect.position + vec2(size.x * start, 0.),
+        vec2(size.x * filled, size.y),
+      ),
+      FillDirection::RightToLeft => Rect::new(
+        rect.position + vec2(size.x * (1. - end), 0.),
+        vec2(size.x * filled, size.y),
+      ),
+      FillDirection::TopToBottom => Rect::new(
+        rect.position + vec2(0., size.y * start),
+        vec2(size.x, size.y * filled),
+      ),
+      FillDirection::BottomToTop => Rect::new(
+        rect.position + vec2(0., size.y * (1. - end)),
+        vec2(size.x, size.y * filled),
+      ),
+    };
+
+    //Draw the foreground at full size and clip it to the filled part,
+    //so that rounded corners keep their shape at any value
+    if filled >= 1. {
+      self.foreground.draw(ctx.paint_target, rect);
+    } else {
+      let mut sub_list = PaintList::new_empty();
+      self.foreground.draw(&mut sub_list, rect);
+      ctx.paint_target.add(PaintClip {
+        rect: fill_rect,
+        child: sub_list,
+      });
+    }
   }
 }
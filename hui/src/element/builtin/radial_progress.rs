@@ -0,0 +1,138 @@
+//! a circular progress indicator element, drawn as an arc or a pie segment
+
+use core::f32::consts::TAU;
+use derive_setters::Setters;
+use glam::{Affine2, Vec2, Vec4};
+use hui_painter::paint::command::{PaintArc, PaintTransform};
+use crate::{
+  element::{MeasureContext, ProcessContext, UiElement},
+  layout::{compute_size, Size, Size2d},
+  measure::Response,
+};
+
+/// Circular progress indicator
+///
+/// Displays `value` as an arc (or a pie segment if `thickness` is `None`),
+/// starting at `start_angle` and going clockwise.\
+/// In indeterminate mode, a fixed-length arc spins around the track instead.
+#[derive(Setters)]
+#[setters(no_std, prefix = "with_")]
+pub struct RadialProgress {
+  /// Current progress, should be in the range 0.0..=1.0
+  pub value: f32,
+
+  /// Size of the element
+  ///
+  /// The indicator is drawn as a circle centered in the element,
+  /// with the diameter equal to the smaller dimension
+  #[setters(into)]
+  pub size: Size2d,
+
+  /// Thickness of the ring, in pixels
+  ///
+  /// If set to `None`, a filled pie segment will be drawn instead
+  #[setters(into)]
+  pub thickness: Option<f32>,
+
+  /// Angle at which the progress arc starts, in radians\
+  /// Measured clockwise from the top (12 o'clock)
+  pub start_angle: f32,
+
+  /// Foreground (progress arc) color
+  #[setters(into)]
+  pub foreground: Vec4,
+
+  /// Background (track) color
+  #[setters(into)]
+  pub background: Vec4,
+
+  /// If set to true, the value is ignored and a spinning arc is displayed instead
+  pub indeterminate: bool,
+
+  /// Length of the spinning arc in indeterminate mode, as a fraction of the full circle
+  pub indeterminate_length: f32,
+
+  /// Rotation speed in indeterminate mode, in revolutions per second
+  pub spin_speed: f32,
+}
+
+impl RadialProgress {
+  pub const DEFAULT_SIZE: f32 = 32.0;
+}
+
+impl Default for RadialProgress {
+  fn default() -> Self {
+    Self {
+      value: 0.,
+      size: Size::Auto.into(),
+      thickness: Some(4.),
+      start_angle: 0.,
+      foreground: (0.0, 0.0, 1.0, 1.0).into(),
+      background: (0.0, 0.0, 0.0, 1.0).into(),
+      indeterminate: false,
+      indeterminate_length: 0.25,
+      spin_speed: 1.,
+    }
+  }
+}
+
+impl UiElement for RadialProgress {
+  fn name(&self) -> &'static str {
+    "radial_progress"
+  }
+
+  fn size(&self) -> Option<Size2d> {
+    Some(self.size)
+  }
+
+  fn measure(&self, ctx: MeasureContext) -> Response {
+    Response {
+      size: compute_size(ctx.layout, self.size, Vec2::splat(Self::DEFAULT_SIZE)),
+      ..Default::default()
+    }
+  }
+
+  fn process(&self, ctx: ProcessContext) {
+    let radius = ctx.measure.size.min_element() / 2.;
+    if radius <= 0. {
+      return
+    }
+    let inner_radius = self.thickness
+      .map(|thickness| (radius - thickness).max(0.))
+      .unwrap_or(0.);
+    let position = ctx.layout.position + ctx.measure.size / 2. - radius;
+
+    let (start_angle, sweep_angle) = if self.indeterminate {
+      let rotation = (ctx.time.elapsed * self.spin_speed).fract();
+      (self.start_angle + rotation * TAU, self.indeterminate_length.clamp(0., 1.) * TAU)
+    } else {
+      (self.start_angle, self.value.clamp(0., 1.) * TAU)
+    };
+
+    //Skip the track if it's fully covered by the progress arc
+    if self.background.w > 0. && (sweep_angle < TAU || self.foreground.w < 1.) {
+      ctx.paint_target.add(PaintTransform {
+        transform: Affine2::from_translation(position),
+        child: PaintArc {
+          color: self.background,
+          radius,
+          inner_radius,
+          ..Default::default()
+        },
+      });
+    }
+    if self.foreground.w > 0. && sweep_angle > 0. {
+      ctx.paint_target.add(PaintTransform {
+        transform: Affine2::from_translation(position),
+        child: PaintArc {
+          color: self.foreground,
+          radius,
+          inner_radius,
+          start_angle,
+          sweep_angle,
+          ..Default::default()
+        },
+      });
+    }
+  }
+}
@@ -0,0 +1,224 @@
+//! a group of mutually exclusive options, only one of which can be selected at a time
+
+use alloc::{borrow::Cow, boxed::Box, vec::Vec};
+use derive_setters::Setters;
+use glam::{vec2, Vec2};
+use crate::{
+  element::{text::Text, MeasureContext, ProcessContext, UiElement},
+  frame::{stack::FrameStackExt, Frame, RectFrame},
+  layout::{compute_size, Direction, LayoutInfo, Size, Size2d},
+  measure::Response,
+  rect::Rect,
+  signal::{trigger::SignalTriggerArg, Signal},
+};
+
+/// A group of mutually exclusive options, only one of which can be selected at a time
+///
+/// Each option consists of an indicator followed by a [`Text`] label\
+/// Clicking an option fires the `on_change` signal with its index
+#[derive(Setters)]
+#[setters(no_std, prefix = "with_")]
+pub struct RadioGroup {
+  /// Labels of the options
+  pub options: Vec<Text>,
+
+  /// Index of the currently selected option
+  ///
+  /// If set to `None` or out of range, no option is selected
+  #[setters(into)]
+  pub selected: Option<usize>,
+
+  /// Size of the element
+  #[setters(into)]
+  pub size: Size2d,
+
+  /// Direction in which the options are laid out
+  pub direction: Direction,
+
+  /// Gap between the options
+  pub gap: f32,
+
+  /// Gap between the indicator and the label
+  pub label_gap: f32,
+
+  /// Size of the indicator, in pixels
+  pub indicator_size: f32,
+
+  /// Indicator frame used for options that are not selected
+  #[setters(skip)]
+  pub indicator: Box<dyn Frame>,
+
+  /// Indicator frame used for the selected option
+  #[setters(skip)]
+  pub indicator_selected: Box<dyn Frame>,
+
+  /// Signal that will be fired with the index of the option when it gets picked
+  #[setters(skip)]
+  pub on_change: Option<SignalTriggerArg<usize>>,
+}
+
+impl Default for RadioGroup {
+  fn default() -> Self {
+    Self {
+      options: Vec::new(),
+      selected: None,
+      size: (Size::Auto, Size::Auto).into(),
+      direction: Direction::Vertical,
+      gap: 5.,
+      label_gap: 5.,
+      indicator_size: 16.,
+      indicator: Box::new(RectFrame::color((0.25, 0.25, 0.25)).with_corner_radius(8.)),
+      indicator_selected: Box::new(
+        RectFrame::color((0.25, 0.25, 0.25)).with_corner_radius(8.)
+          .stack(RectFrame::color((0.0, 0.0, 1.0)).with_corner_radius(4.).with_inset(4.))
+      ),
+      on_change: None,
+    }
+  }
+}
+
+impl RadioGroup {
+  pub fn new(options: impl IntoIterator<Item = impl Into<Cow<'static, str>>>) -> Self {
+    Self {
+      options: options.into_iter().map(Text::new).collect(),
+      ..Default::default()
+    }
+  }
+
+  pub fn on_change<S: Signal, T: Fn(usize) -> S + 'static>(self, f: T) -> Self {
+    Self {
+      on_change: Some(SignalTriggerArg::new(f)),
+      ..self
+    }
+  }
+
+  pub fn with_indicator(mut self, frame: impl Frame + 'static) -> Self {
+    self.indicator = Box::new(frame);
+    self
+  }
+
+  pub fn with_indicator_selected(mut self, frame: impl Frame + 'static) -> Self {
+    self.indicator_selected = Box::new(frame);
+    self
+  }
+
+  /// Measure the labels of all options
+  fn measure_labels(&self, ctx: &MeasureContext) -> Vec<Response> {
+    let layout = LayoutInfo {
+      position: Vec2::ZERO,
+      max_size: ctx.layout.max_size - vec2(self.indicator_size + self.label_gap, 0.),
+      direction: self.direction,
+      remaining_space: None,
+    };
+    self.options.iter().map(|label| {
+      label.measure(MeasureContext {
+        painter: ctx.painter,
+        layout: &layout,
+        state: ctx.state,
+        current_font: ctx.current_font,
+      })
+    }).collect()
+  }
+
+  /// Get the size of a single option, given the measured size of its label
+  fn option_size(&self, label_size: Vec2) -> Vec2 {
+    vec2(
+      self.indicator_size + self.label_gap + label_size.x,
+      self.indicator_size.max(label_size.y),
+    )
+  }
+}
+
+impl UiElement for RadioGroup {
+  fn name(&self) -> &'static str {
+    "radio_group"
+  }
+
+  fn size(&self) -> Option<Size2d> {
+    Some(self.size)
+  }
+
+  fn measure(&self, ctx: MeasureContext) -> Response {
+    let mut size = Vec2::ZERO;
+    for (idx, label) in self.measure_labels(&ctx).iter().enumerate() {
+      let option_size = self.option_size(label.size);
+      let gap = if idx > 0 { self.gap } else { 0. };
+      match self.direction {
+        Direction::Horizontal => {
+          size.x += gap + option_size.x;
+          size.y = size.y.max(option_size.y);
+        },
+        Direction::Vertical => {
+          size.x = size.x.max(option_size.x);
+          size.y += gap + option_size.y;
+        },
+      }
+    }
+    Response {
+      size: compute_size(ctx.layout, self.size, size),
+      ..Default::default()
+    }
+  }
+
+  fn process(&self, ctx: ProcessContext) {
+    let labels = self.measure_labels(&MeasureContext {
+      painter: ctx.painter,
+      layout: ctx.layout,
+      state: ctx.state,
+      current_font: ctx.current_font,
+    });
+
+    let mut position = ctx.layout.position;
+    for (idx, (label, label_measure)) in self.options.iter().zip(labels.iter()).enumerate() {
+      let option_size = self.option_size(label_measure.size);
+      let option_rect = Rect { position, size: option_size };
+
+      //Draw the indicator, centered vertically within the option
+      let indicator_frame = if self.selected == Some(idx) {
+        &self.indicator_selected
+      } else {
+        &self.indicator
+      };
+      indicator_frame.draw(ctx.paint_target, Rect {
+        position: position + vec2(0., (option_size.y - self.indicator_size) / 2.),
+        size: Vec2::splat(self.indicator_size),
+      });
+
+      //Draw the label
+      label.process(ProcessContext {
+        painter: ctx.painter,
+        measure: label_measure,
+        layout: &LayoutInfo {
+          position: position + vec2(
+            self.indicator_size + self.label_gap,
+            (option_size.y - label_measure.size.y) / 2.,
+          ),
+          max_size: label_measure.size,
+          direction: self.direction,
+          remaining_space: None,
+        },
+        paint_target: ctx.paint_target,
+        paint_overlay: ctx.paint_overlay,
+        state: ctx.state,
+        current_font: ctx.current_font,
+        input: ctx.input,
+        signal: ctx.signal,
+        time: ctx.time,
+        root_rect: ctx.root_rect,
+        overlay_occluders: ctx.overlay_occluders,
+      });
+
+      //Handle clicks
+      if self.selected != Some(idx) && ctx.input.check_click(option_rect).is_some() {
+        if let Some(signal) = &self.on_change {
+          signal.fire(ctx.signal, idx);
+        }
+      }
+
+      match self.direction {
+        Direction::Horizontal => position.x += option_size.x + self.gap,
+        Direction::Vertical => position.y += option_size.y + self.gap,
+      }
+    }
+  }
+}
@@ -0,0 +1,299 @@
+//! a scrollable container element that clips its children
+
+use core::hash::Hash;
+use alloc::boxed::Box;
+use derive_setters::Setters;
+use glam::{vec2, Vec2};
+use hui_painter::paint::command::{PaintClip, PaintList};
+use crate::{
+  element::{container::Container, scrollbar::Scrollbar, ElementList, MeasureContext, ProcessContext, UiElement},
+  frame::{Frame, RectFrame},
+  layout::{compute_size, Alignment2d, Direction, LayoutInfo, Size, Size2d, WrapBehavior},
+  measure::Response,
+  rect::{Rect, Sides},
+  state::{state_id, State, StateId},
+};
+
+/// State of a [`ScrollView`] element
+#[derive(Clone, Copy, Debug, Default)]
+pub struct ScrollViewState {
+  /// Current scroll offset, in pixels
+  pub offset: f32,
+
+  /// Current scrolling velocity, in pixels per second (only used if kinetic scrolling is enabled)
+  pub velocity: f32,
+
+  /// Scroll offset at the time the scrollbar started being dragged
+  pub drag_start: Option<f32>,
+}
+
+impl State for ScrollViewState {}
+
+/// A scrollable container element
+///
+/// Children are laid out the same way as in a [`Container`] (but never wrapped),
+/// with the primary axis (set by the direction) being unbounded.\
+/// Anything outside of the element's rect is clipped
+///
+/// Can be scrolled using the mouse wheel or by dragging the scrollbar\
+/// The scroll offset is stored in the [`StateRepo`](crate::state::StateRepo) under the given id
+#[derive(Setters)]
+#[setters(no_std, prefix = "with_")]
+pub struct ScrollView {
+  /// Id used to store the state of the element
+  #[setters(skip)]
+  pub id: StateId,
+
+  /// Inner container used to lay out the children elements
+  #[setters(skip)]
+  content: Container,
+
+  /// Size of the element (visible area)
+  ///
+  /// If set to `Size::Auto`, the element will be as large as its contents, up to the maximum size allowed by the parent
+  #[setters(into)]
+  pub size: Size2d,
+
+  /// Enables kinetic scrolling if set\
+  /// The value is the ratio of the velocity preserved every 1/60th of a second, in range `0.0..1.0`
+  ///
+  /// If not set, mouse wheel scrolls the contents instantly\
+  /// Kinetic scrolling relies on the frame timing (see [`UiInstance::advance_time`](crate::UiInstance::advance_time))
+  #[setters(into)]
+  pub kinetic: Option<f32>,
+
+  /// Width of the scrollbar, in pixels
+  ///
+  /// Set to `0.0` to hide the scrollbar
+  pub scrollbar_width: f32,
+
+  /// Minimum length of the scrollbar handle, in pixels
+  pub scrollbar_min_length: f32,
+
+  /// Scrollbar track frame
+  #[setters(skip)]
+  pub scrollbar_track: Box<dyn Frame>,
+
+  /// Scrollbar handle frame
+  #[setters(skip)]
+  pub scrollbar: Box<dyn Frame>,
+}
+
+impl ScrollView {
+  pub fn new(id: impl Hash) -> Self {
+    Self {
+      id: state_id(id),
+      content: Container::default()
+        .with_wrap(WrapBehavior::Disable),
+      size: (Size::Relative(1.), Size::Relative(1.)).into(),
+      kinetic: None,
+      scrollbar_width: 8.,
+      scrollbar_min_length: 16.,
+      scrollbar_track: Box::new(RectFrame::color((0.1, 0.1, 0.1, 0.5))),
+      scrollbar: Box::new(RectFrame::color((0.5, 0.5, 0.5))),
+    }
+  }
+
+  pub fn with_children(mut self, ui: impl FnOnce(&mut ElementList)) -> Self {
+    self.content = self.content.with_children(ui);
+    self
+  }
+
+  pub fn with_direction(mut self, direction: Direction) -> Self {
+    self.content.direction = direction;
+    self
+  }
+
+  pub fn with_gap(mut self, gap: f32) -> Self {
+    self.content.gap = gap;
+    self
+  }
+
+  pub fn with_padding(mut self, padding: impl Into<Sides<f32>>) -> Self {
+    self.content.padding = padding.into();
+    self
+  }
+
+  pub fn with_align(mut self, align: impl Into<Alignment2d>) -> Self {
+    self.content.align = align.into();
+    self
+  }
+
+  pub fn with_scrollbar_track(mut self, frame: impl Frame + 'static) -> Self {
+    self.scrollbar_track = Box::new(frame);
+    self
+  }
+
+  pub fn with_scrollbar(mut self, frame: impl Frame + 'static) -> Self {
+    self.scrollbar = Box::new(frame);
+    self
+  }
+
+  /// Get the component of the vector along the scrolling axis
+  fn primary(&self, v: Vec2) -> f32 {
+    match self.content.direction {
+      Direction::Horizontal => v.x,
+      Direction::Vertical => v.y,
+    }
+  }
+
+  /// Create a vector pointing along the scrolling axis
+  fn primary_vec(&self, value: f32) -> Vec2 {
+    match self.content.direction {
+      Direction::Horizontal => vec2(value, 0.),
+      Direction::Vertical => vec2(0., value),
+    }
+  }
+
+  /// Create a vector pointing along the cross axis
+  fn cross_vec(&self, value: f32) -> Vec2 {
+    match self.content.direction {
+      Direction::Horizontal => vec2(0., value),
+      Direction::Vertical => vec2(value, 0.),
+    }
+  }
+
+  /// Measure the children, laid out within the given visible area
+  fn measure_content(&self, ctx: &MeasureContext, max_size: Vec2) -> Response {
+    self.content.measure(MeasureContext {
+      painter: ctx.painter,
+      layout: &LayoutInfo {
+        position: ctx.layout.position,
+        max_size,
+        direction: self.content.direction,
+        remaining_space: None,
+      },
+      state: ctx.state,
+      current_font: ctx.current_font,
+    })
+  }
+}
+
+impl UiElement for ScrollView {
+  fn name(&self) -> &'static str {
+    "scroll_view"
+  }
+
+  fn size(&self) -> Option<Size2d> {
+    Some(self.size)
+  }
+
+  fn measure(&self, ctx: MeasureContext) -> Response {
+    //Children are laid out as if the container was the size of the visible area
+    //(however they are never wrapped, and can exceed it on the primary axis)
+    let visible_size = compute_size(ctx.layout, self.size, ctx.layout.max_size);
+    let mut content = self.measure_content(&ctx, visible_size);
+
+    //If the contents overflow, lay them out again with the space for the scrollbar reserved
+    if self.scrollbar_width > 0. && self.primary(content.size) > self.primary(visible_size) {
+      content = self.measure_content(&ctx, (visible_size - self.cross_vec(self.scrollbar_width)).max(Vec2::ZERO));
+    }
+    Response {
+      size: compute_size(ctx.layout, self.size, content.size.min(ctx.layout.max_size)),
+      user_data: Some(Box::new(content)),
+      ..Default::default()
+    }
+  }
+
+  fn process(&self, ctx: ProcessContext) {
+    let content_measure: &Response = ctx.measure.user_data
+      .as_ref().expect("no user data attached to scroll view")
+      .downcast_ref().expect("invalid user data type");
+
+    let rect = ctx.measure.rect(ctx.layout.position);
+    let viewport_length = self.primary(rect.size);
+    let max_offset = (self.primary(content_measure.size) - viewport_length).max(0.);
+
+    //Scrollbar geometry
+    let show_scrollbar = max_offset > 0. && self.scrollbar_width > 0.;
+    let (track_rect, content_rect) = match self.content.direction {
+      Direction::Horizontal => (
+        Rect::new(rect.position + vec2(0., rect.size.y - self.scrollbar_width), vec2(rect.size.x, self.scrollbar_width)),
+        Rect::new(rect.position, rect.size - vec2(0., self.scrollbar_width)),
+      ),
+      Direction::Vertical => (
+        Rect::new(rect.position + vec2(rect.size.x - self.scrollbar_width, 0.), vec2(self.scrollbar_width, rect.size.y)),
+        Rect::new(rect.position, rect.size - vec2(self.scrollbar_width, 0.)),
+      ),
+    };
+    let content_rect = if show_scrollbar { content_rect } else { rect };
+    let scrollbar = Scrollbar::new(track_rect, self.content.direction, self.primary(content_measure.size), self.scrollbar_min_length);
+
+    let state = ctx.state.acquire_mut_or_default::<ScrollViewState>(self.id);
+
+    //Kinetic scrolling
+    if let Some(friction) = self.kinetic {
+      state.offset += state.velocity * ctx.time.delta;
+      state.velocity *= friction.clamp(0., 1.).powf(ctx.time.delta * 60.);
+      if state.velocity.abs() < 6. {
+        state.velocity = 0.;
+      }
+    }
+
+    //Scrollbar dragging
+    if show_scrollbar {
+      if let Some(offset) = scrollbar.process(&ctx.input, state.offset, &mut state.drag_start) {
+        state.offset = offset;
+        state.velocity = 0.;
+      }
+    } else {
+      state.drag_start = None;
+    }
+
+    //Keep the offset in bounds
+    let clamped_offset = state.offset.clamp(0., max_offset);
+    if clamped_offset != state.offset {
+      state.offset = clamped_offset;
+      state.velocity = 0.;
+    }
+    let offset = state.offset;
+
+    //Process the children, clipping both the paint commands and the input to the visible area
+    let mut sub_list = PaintList::new_empty();
+    self.content.process(ProcessContext {
+      painter: ctx.painter,
+      measure: content_measure,
+      layout: &LayoutInfo {
+        position: ctx.layout.position - self.primary_vec(offset.round()),
+        max_size: content_rect.size,
+        direction: self.content.direction,
+        remaining_space: None,
+      },
+      paint_target: &mut sub_list,
+      paint_overlay: ctx.paint_overlay,
+      state: ctx.state,
+      current_font: ctx.current_font,
+      input: ctx.input.with_clip(content_rect),
+      signal: ctx.signal,
+      time: ctx.time,
+      root_rect: ctx.root_rect,
+      overlay_occluders: ctx.overlay_occluders,
+    });
+    ctx.paint_target.add(PaintClip {
+      rect: content_rect,
+      child: sub_list,
+    });
+
+    //Mouse wheel
+    //(handled after the children, so that the nested scrollable elements can consume it first)
+    if max_offset > 0. && ctx.input.check_hover(rect) {
+      let wheel = ctx.input.consume_wheel_delta();
+      //Most mice only have a vertical wheel, so use it for horizontal scrolling as well
+      let delta = match self.content.direction {
+        Direction::Horizontal if wheel.x == 0. => wheel.y,
+        _ => self.primary(wheel),
+      };
+      let state = ctx.state.acquire_mut_or_default::<ScrollViewState>(self.id);
+      match self.kinetic {
+        //Initial velocity is chosen so that the total distance traveled matches the wheel movement
+        Some(friction) => state.velocity -= delta * -friction.clamp(0.001, 0.999).ln() * 60.,
+        None => state.offset = (state.offset - delta).clamp(0., max_offset),
+      }
+    }
+
+    //Draw the scrollbar
+    if show_scrollbar {
+      scrollbar.draw(ctx.paint_target, &*self.scrollbar_track, &*self.scrollbar, offset);
+    }
+  }
+}
@@ -0,0 +1,108 @@
+//! scrollbar shared by the scrollable elements
+
+use glam::{vec2, Vec2};
+use hui_painter::paint::command::PaintList;
+use crate::{
+  frame::Frame,
+  input::{InputCtx, MouseButton},
+  layout::Direction,
+  rect::Rect,
+};
+
+/// Geometry and input handling of a scrollbar
+///
+/// Scroll position is measured in pixels of the content, in range `0.0..=max_position`
+pub(crate) struct Scrollbar {
+  /// Rect of the scrollbar track
+  track: Rect,
+
+  /// Scrolling axis
+  direction: Direction,
+
+  /// Length of the handle, in pixels
+  handle_length: f32,
+
+  /// Distance the handle can move along the track, in pixels
+  handle_travel: f32,
+
+  /// Maximum scroll position
+  max_position: f32,
+}
+
+impl Scrollbar {
+  /// Create a scrollbar for the content of the given length\
+  /// (the track is assumed to be as long as the visible area)
+  pub fn new(track: Rect, direction: Direction, content_length: f32, min_handle_length: f32) -> Self {
+    let track_length = primary(direction, track.size);
+    let handle_length = (track_length / content_length * track_length)
+      .max(min_handle_length)
+      .min(track_length);
+    Self {
+      track,
+      direction,
+      handle_length,
+      handle_travel: (track_length - handle_length).max(0.),
+      max_position: (content_length - track_length).max(0.),
+    }
+  }
+
+  /// Get the rect of the handle at the given scroll position
+  pub fn handle_rect(&self, position: f32) -> Rect {
+    let fraction = match self.max_position > 0. {
+      true => position.clamp(0., self.max_position) / self.max_position,
+      false => 0.,
+    };
+    let (offset, length) = (fraction * self.handle_travel, self.handle_length);
+    match self.direction {
+      Direction::Horizontal => Rect::new(self.track.position + vec2(offset, 0.), vec2(length, self.track.size.y)),
+      Direction::Vertical => Rect::new(self.track.position + vec2(0., offset), vec2(self.track.size.x, length)),
+    }
+  }
+
+  /// Handle clicking and dragging the scrollbar
+  ///
+  /// `drag_start` should be persisted between frames\
+  /// Returns the new scroll position, if changed
+  pub fn process(&self, input: &InputCtx, position: f32, drag_start: &mut Option<f32>) -> Option<f32> {
+    //The handle can't be moved if it fills the whole track
+    if self.handle_travel <= 0. {
+      *drag_start = None;
+      return None
+    }
+
+    let mut new_position = None;
+    if input.mouse_button_just_pressed(MouseButton::Primary) && input.check_hover(self.track) {
+      //Clicking the track outside of the handle moves the handle to the pointer
+      let mut start = position;
+      if !input.check_hover(self.handle_rect(position)) {
+        let pointer = primary(self.direction, input.mouse_position() - self.track.position);
+        start = ((pointer - self.handle_length / 2.) / self.handle_travel * self.max_position)
+          .clamp(0., self.max_position);
+        new_position = Some(start);
+      }
+      *drag_start = Some(start);
+    }
+    match (*drag_start, input.check_active(self.track)) {
+      (Some(start), Some(res)) => {
+        let delta = primary(self.direction, res.position_in_rect - res.start_position_in_rect);
+        new_position = Some(start + delta / self.handle_travel * self.max_position);
+      },
+      _ => *drag_start = None,
+    }
+    new_position.map(|position| position.clamp(0., self.max_position))
+  }
+
+  /// Draw the track and the handle at the given scroll position
+  pub fn draw(&self, target: &mut PaintList, track: &dyn Frame, handle: &dyn Frame, position: f32) {
+    track.draw(target, self.track);
+    handle.draw(target, self.handle_rect(position));
+  }
+}
+
+/// Get the component of the vector along the given axis
+fn primary(direction: Direction, v: Vec2) -> f32 {
+  match direction {
+    Direction::Horizontal => v.x,
+    Direction::Vertical => v.y,
+  }
+}
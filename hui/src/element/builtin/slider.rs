@@ -1,5 +1,6 @@
 //! a slider element that allows selecting a value in a range
 
+use core::hash::Hash;
 use alloc::boxed::Box;
 use derive_setters::Setters;
 use glam::{Vec2, vec2};
@@ -7,14 +8,14 @@ use glam::{Vec2, vec2};
 use crate::{
   element::{MeasureContext, ProcessContext, UiElement},
   frame::{Frame, RectFrame},
-  layout::{compute_size, Size2d},
+  input::{KeyboardKey, MouseButton},
+  layout::{compute_size, Direction, Size2d},
   measure::Response,
+  rect::Rect,
   signal::{trigger::SignalTriggerArg, Signal},
+  state::{state_id, State, StateId},
 };
 
-//TODO: use state for slider?
-// ^ useful if the user only hanldes the drag end event or has large step sizes with relative mode
-
 //TODO: adopt frame api here
 
 /// Follow mode for the slider
@@ -27,20 +28,75 @@ pub enum SliderFollowMode {
   Absolute,
 
   /// Slider will change based on the difference between the current and starting mouse position
-  ///
-  /// This is an experimental option and does not currently work well for sliders with large step sizes
   Relative,
 }
 
+/// State of a [`Slider`] element
+#[derive(Clone, Copy, Debug, Default)]
+pub struct SliderState {
+  /// Unsnapped value of the handle being dragged (`None` if not currently dragged)
+  ///
+  /// Accumulating the unsnapped value allows relative sliders with large steps to work correctly
+  pub drag_value: Option<f32>,
+
+  /// Index of the handle that was last interacted with (`0` for the lower handle, `1` for the upper one)
+  pub handle: usize,
+
+  /// Whether the slider has keyboard focus
+  pub focused: bool,
+}
+
+impl State for SliderState {}
+
 /// A slider element that allows selecting a value in a range
+///
+/// In range mode (if [`Slider::value_end`] is set), the slider has two handles,
+/// allowing selection of a sub-range instead of a single value
+///
+/// While focused (after being clicked), the value can be changed using arrow keys\
+/// (in range mode, the handle that was last interacted with is moved)
 #[derive(Setters)]
 #[setters(no_std, prefix = "with_")]
 pub struct Slider {
-  /// Value of the slider, should be in range 0..1
+  /// Id used to store the state of the element
+  #[setters(skip)]
+  pub id: StateId,
+
+  /// Value of the slider, should be in range `min..=max`
   ///
+  /// In range mode, this is the value of the lower handle\
   /// Out of range values will be clamped
   pub value: f32,
 
+  /// Value of the upper handle, should be in range `value..=max`
+  ///
+  /// If set, the slider works in range mode
+  #[setters(into)]
+  pub value_end: Option<f32>,
+
+  /// Minimum value of the slider
+  pub min: f32,
+
+  /// Maximum value of the slider
+  ///
+  /// If it's lower than `min`, the value is always `min`
+  pub max: f32,
+
+  /// Step the value is snapped to\
+  /// If set to `None`, the value is not snapped
+  #[setters(into)]
+  pub step: Option<f32>,
+
+  /// Change of the value per arrow key press\
+  /// If set to `None`, the step is used (or 1% of the range if there is no step)
+  #[setters(into)]
+  pub key_step: Option<f32>,
+
+  /// Orientation of the slider
+  ///
+  /// Horizontal sliders increase from left to right, vertical sliders from bottom to top
+  pub direction: Direction,
+
   /// Size of the element
   #[setters(into)]
   pub size: Size2d,
@@ -57,7 +113,23 @@ pub struct Slider {
   #[setters(skip)]
   pub handle: Box<dyn Frame>,
 
-  /// Track height *relative to the slider height*\
+  /// Tick mark frame\
+  /// If set, tick marks are drawn at every `tick_interval` (below the handles)
+  #[setters(skip)]
+  pub tick: Option<Box<dyn Frame>>,
+
+  /// Interval between the tick marks\
+  /// If set to `None`, the step is used
+  #[setters(into)]
+  pub tick_interval: Option<f32>,
+
+  /// Tick mark size
+  ///
+  /// - Width is *static* and specified in *pixels*
+  /// - Length (across the slider) is *relative* to the slider thickness
+  pub tick_size: (f32, f32),
+
+  /// Track thickness *relative to the slider thickness*\
   ///
   /// Range: 0.0..=1.0
   pub track_height: f32,
@@ -66,8 +138,8 @@ pub struct Slider {
   ///
   /// Please be aware that:
   ///
-  /// - Width is *static* and specified in *pixels* (e.g. `15.0`)
-  /// - Height is *relative* to the slider height,\
+  /// - Length (along the slider) is *static* and specified in *pixels* (e.g. `15.0`)
+  /// - Thickness is *relative* to the slider thickness,\
   ///   ...and is specified as a *ratio* in range `0.0..=1.0`
   pub handle_size: (f32, f32),
 
@@ -76,34 +148,53 @@ pub struct Slider {
 
   #[setters(skip)]
   pub on_change: Option<SignalTriggerArg<f32>>,
+
+  /// Signal fired with the new `(start, end)` values in range mode
+  #[setters(skip)]
+  pub on_range_change: Option<SignalTriggerArg<(f32, f32)>>,
 }
 
-impl Default for Slider {
-  fn default() -> Self {
+impl Slider {
+  pub const DEFAULT_HEIGHT: f32 = 20.0;
+
+  pub fn new(id: impl Hash, value: f32) -> Self {
     Self {
-      value: 0.0,
+      id: state_id(id),
+      value,
+      value_end: None,
+      min: 0.,
+      max: 1.,
+      step: None,
+      key_step: None,
+      direction: Direction::Horizontal,
       size: Size2d::default(),
       handle: Box::new(RectFrame::color((0.0, 0.0, 1.))),
       track: Box::new(RectFrame::color((0.5, 0.5, 0.5))),
       track_active: Box::new(RectFrame::color((0.0, 0.0, 0.75))),
+      tick: None,
+      tick_interval: None,
+      tick_size: (2., 0.5),
       track_height: 0.25,
       handle_size: (15.0, 1.),
       follow_mode: SliderFollowMode::default(),
-      on_change: None
+      on_change: None,
+      on_range_change: None,
     }
   }
-}
 
-impl Slider {
-  pub const DEFAULT_HEIGHT: f32 = 20.0;
-
-  pub fn new(value: f32) -> Self {
+  /// Create a slider in range mode, with two handles
+  pub fn new_range(id: impl Hash, start: f32, end: f32) -> Self {
     Self {
-      value,
-      ..Default::default()
+      value_end: Some(end),
+      ..Self::new(id, start)
     }
   }
 
+  /// Set both the minimum and maximum value
+  pub fn with_range(self, min: f32, max: f32) -> Self {
+    Self { min, max, ..self }
+  }
+
   pub fn on_change<S: Signal, T: Fn(f32) -> S + 'static>(self, f: T) -> Self {
     Self {
       on_change: Some(SignalTriggerArg::new(f)),
@@ -111,6 +202,13 @@ impl Slider {
     }
   }
 
+  pub fn on_range_change<S: Signal, T: Fn((f32, f32)) -> S + 'static>(self, f: T) -> Self {
+    Self {
+      on_range_change: Some(SignalTriggerArg::new(f)),
+      ..self
+    }
+  }
+
   pub fn with_track(mut self, track: impl Frame + 'static) -> Self {
     self.track = Box::new(track);
     self
@@ -125,6 +223,65 @@ impl Slider {
     self.handle = Box::new(handle);
     self
   }
+
+  pub fn with_tick(mut self, tick: impl Frame + 'static) -> Self {
+    self.tick = Some(Box::new(tick));
+    self
+  }
+
+  /// Snap the value to the step and clamp it to the range
+  fn constrain(&self, value: f32) -> f32 {
+    let value = match self.step {
+      Some(step) if step > 0. => self.min + ((value - self.min) / step).round() * step,
+      _ => value,
+    };
+    //Not using clamp, as it panics if min > max
+    value.min(self.max).max(self.min)
+  }
+
+  /// Convert the value to a ratio in range `0.0..=1.0`
+  fn to_ratio(&self, value: f32) -> f32 {
+    match self.max > self.min {
+      true => ((value - self.min) / (self.max - self.min)).clamp(0., 1.),
+      false => 0.,
+    }
+  }
+
+  /// Get the (length, thickness) of the vector, relative to the slider orientation
+  fn split(&self, v: Vec2) -> (f32, f32) {
+    match self.direction {
+      Direction::Horizontal => (v.x, v.y),
+      Direction::Vertical => (v.y, v.x),
+    }
+  }
+
+  /// Get the rect spanning the given range along the slider,
+  /// and the given range relative to the slider thickness (centered)
+  ///
+  /// Vertical sliders are flipped, so that the `start` is at the bottom
+  fn part_rect(&self, rect: Rect, start: f32, length: f32, thickness_ratio: f32) -> Rect {
+    let (slider_length, thickness) = self.split(rect.size);
+    let cross_start = thickness * (0.5 - thickness_ratio / 2.);
+    let cross_length = thickness * thickness_ratio;
+    match self.direction {
+      Direction::Horizontal => Rect::new(
+        rect.position + vec2(start, cross_start),
+        vec2(length, cross_length),
+      ),
+      Direction::Vertical => Rect::new(
+        rect.position + vec2(cross_start, slider_length - start - length),
+        vec2(cross_length, length),
+      ),
+    }
+  }
+
+  /// Convert the pointer position (relative to the slider rect) to a position along the slider, in pixels
+  fn pointer_position(&self, rect: Rect, position: Vec2) -> f32 {
+    match self.direction {
+      Direction::Horizontal => position.x,
+      Direction::Vertical => rect.size.y - position.y,
+    }
+  }
 }
 
 impl UiElement for Slider {
@@ -133,8 +290,12 @@ impl UiElement for Slider {
   }
 
   fn measure(&self, ctx: MeasureContext) -> Response {
+    let default_size = match self.direction {
+      Direction::Horizontal => vec2(ctx.layout.max_size.x, Self::DEFAULT_HEIGHT),
+      Direction::Vertical => vec2(Self::DEFAULT_HEIGHT, ctx.layout.max_size.y),
+    };
     Response {
-      size: compute_size(ctx.layout, self.size, (ctx.layout.max_size.x, Self::DEFAULT_HEIGHT).into()),
+      size: compute_size(ctx.layout, self.size, default_size),
       ..Default::default()
     }
   }
@@ -142,84 +303,145 @@ impl UiElement for Slider {
   fn process(&self, ctx: ProcessContext) {
     //XXX: some of these assumptions are wrong if the  corners are rounded
 
-    //Compute handle size:
-    // This is kinda counter-intuitive, but if the handle is transparent, we treat it as completely disabled
-    // To prevent confusing offset from the edge of the slider, we set the handle size to 0
-    // let handle_size = if self.handle_color.is_transparent() {
-    //   Vec2::ZERO
-    // } else {
-    //   vec2(15., ctx.measure.size.y)
-    // };
-    let handle_size = vec2(self.handle_size.0, self.handle_size.1 * ctx.measure.size.y);
+    let rect = ctx.measure.rect(ctx.layout.position);
+    let (slider_length, _) = self.split(rect.size);
+
+    //Distance the handle can travel along the slider
+    let handle_length = self.handle_size.0;
+    let travel = slider_length - handle_length;
+
+    //Values of the handles
+    let values = match self.value_end {
+      Some(end) => [self.value, end],
+      None => [self.value, self.value],
+    };
+    let handle_count = if self.value_end.is_some() { 2 } else { 1 };
+
+    //handle events
+    let state = ctx.state.acquire_mut_or_default::<SliderState>(self.id);
+    let mut new_values = None;
+
+    if ctx.input.mouse_button_just_pressed(MouseButton::Primary) {
+      state.focused = ctx.input.check_hover(rect);
+      if state.focused {
+        //Pick the handle closest to the pointer (if both are at the same position, the one on the side of the pointer)
+        let pointer = self.pointer_position(rect, ctx.input.mouse_position() - rect.position);
+        let distance = |value: f32| pointer - (travel * self.to_ratio(value) + handle_length / 2.);
+        state.handle = match distance(values[0]).abs().total_cmp(&distance(values[1]).abs()) {
+          core::cmp::Ordering::Less => 0,
+          core::cmp::Ordering::Greater => 1,
+          core::cmp::Ordering::Equal => (distance(values[1]) > 0.) as usize,
+        }.min(handle_count - 1);
+        state.drag_value = Some(values[state.handle]);
+      }
+    }
+
+    match (state.drag_value, ctx.input.check_active(rect)) {
+      (Some(drag_value), Some(res)) => {
+        let pointer = self.pointer_position(rect, res.position_in_rect);
+        let last_pointer = self.pointer_position(rect, res.last_position_in_rect);
+        let drag_value = match self.follow_mode {
+          SliderFollowMode::Absolute => {
+            self.min + ((pointer - handle_length / 2.) / travel).clamp(0., 1.) * (self.max - self.min)
+          },
+          SliderFollowMode::Relative => {
+            let delta = pointer - last_pointer;
+            let delta_ratio = delta / travel;
+            (drag_value + delta_ratio * (self.max - self.min)).min(self.max).max(self.min)
+          }
+        };
+        state.drag_value = Some(drag_value);
+        let mut updated = values;
+        updated[state.handle] = self.constrain(drag_value);
+        new_values = Some(updated);
+      },
+      _ => state.drag_value = None,
+    }
+
+    //Keyboard input
+    if state.focused {
+      let key_step = self.key_step
+        .or(self.step)
+        .unwrap_or((self.max - self.min) / 100.);
+      for key in ctx.input.keys_pressed() {
+        let mut updated = new_values.unwrap_or(values);
+        let value = &mut updated[state.handle];
+        *value = match key {
+          KeyboardKey::Right | KeyboardKey::Up => self.constrain(*value + key_step),
+          KeyboardKey::Left | KeyboardKey::Down => self.constrain(*value - key_step),
+          KeyboardKey::Home => self.min,
+          KeyboardKey::End => self.max,
+          KeyboardKey::Escape => {
+            state.focused = false;
+            break
+          },
+          _ => continue,
+        };
+        new_values = Some(updated);
+      }
+    }
+
+    if let Some([start, end]) = new_values.filter(|&updated| updated != values) {
+      match self.value_end {
+        None => if let Some(signal) = &self.on_change {
+          signal.fire(ctx.signal, start);
+        },
+        //Handles are not allowed to cross each other
+        Some(_) => if let Some(signal) = &self.on_range_change {
+          let range = match state.handle {
+            0 => (start.min(end), end),
+            _ => (start, end.max(start)),
+          };
+          signal.fire(ctx.signal, range);
+        },
+      }
+    }
+
+    //In range mode, the active part of the track spans between the handles
+    let start = match self.value_end {
+      Some(_) => self.to_ratio(values[0]),
+      None => 0.,
+    };
+    let end = self.to_ratio(values[1]);
+    let handle_covers_track = self.handle.covers_opaque() && (self.handle_size.1 >= self.track_height);
 
     //Draw the track
-    //If the active part is opaque and value >= 1., we don't need to draw the background as the active part will cover it
+    //If the active part is opaque and covers the whole track, we don't need to draw the background as the active part will cover it
     //However, if the handle is not opaque, we need to draw the background as the active part won't quite reach the end
     //Of corse, if it's fully transparent, we don't need to draw it either
-    // if !(self.track_color.is_transparent() || (self.track_active_color.is_opaque() && self.handle_color.is_opaque() && self.value >= 1.)) {
-    if !(self.track_active.covers_opaque() && self.handle.covers_opaque() && (self.handle_size.1 >= self.track_height) && self.value >= 1.) {
-      self.track.draw(
-        ctx.paint_target,
-        (
-          ctx.layout.position + ctx.measure.size * vec2(0., 0.5 - self.track_height / 2.),
-          ctx.measure.size * vec2(1., self.track_height),
-        ).into()
-      );
+    if !(self.track_active.covers_opaque() && handle_covers_track && start <= 0. && end >= 1.) {
+      self.track.draw(ctx.paint_target, self.part_rect(rect, 0., slider_length, self.track_height));
     }
 
     //"Active" part of the track
-    //We can skip drawing it if it's fully transparent or value <= 0.
+    //We can skip drawing it if it's empty
     //But if the handle is not opaque, it should be visible even if value is zero
-    // if !(self.track_active_color.is_transparent() || (self.value <= 0. && self.handle_color.is_opaque())) {
-    if !(self.handle.covers_opaque() && (self.handle_size.1 >= self.track_height) && self.value <= 0.) {
-      self.track_active.draw(
-        ctx.paint_target,
-        (
-          ctx.layout.position + ctx.measure.size * vec2(0., 0.5 - self.track_height / 2.),
-          (ctx.measure.size - handle_size * Vec2::X) * vec2(self.value, self.track_height) + handle_size * Vec2::X / 2.,
-        ).into()
-      );
-    }
-
-    // The handle
-    // if handle_size.x != 0. && !self.handle_color.is_transparent() {
-    //   let value = self.value.clamp(0., 1.);
-    //   ctx.draw.add(UiDrawCommand::Rectangle {
-    //     position: ctx.layout.position + ((ctx.measure.size.x - handle_size.x) * value) * Vec2::X,
-    //     size: handle_size,
-    //     color: self.handle_color.into(),
-    //     texture: None,
-    //     rounded_corners: None,
-    //   });
-    // }
-    if (self.handle_size.0 > 0. && self.handle_size.1 > 0.) {
-      self.handle.draw(
-        ctx.paint_target,
-        (
-          ctx.layout.position +
-            ((ctx.measure.size.x - handle_size.x) * self.value) * Vec2::X +
-            ctx.measure.size.y * ((1. - self.handle_size.1) * 0.5) * Vec2::Y,
-          handle_size,
-        ).into()
-      );
+    if !(handle_covers_track && end <= start) {
+      let active_start = match self.value_end {
+        Some(_) => travel * start + handle_length / 2.,
+        None => 0.,
+      };
+      let active_end = travel * end + handle_length / 2.;
+      self.track_active.draw(ctx.paint_target, self.part_rect(rect, active_start, active_end - active_start, self.track_height));
     }
 
-    //handle events
-    if let Some(res) = ctx.input.check_active(ctx.measure.rect(ctx.layout.position)) {
-      let new_value = match self.follow_mode {
-        SliderFollowMode::Absolute => {
-          ((res.position_in_rect.x - handle_size.x / 2.) / (ctx.measure.size.x - handle_size.x)).clamp(0., 1.)
-        },
-        SliderFollowMode::Relative => {
-          let delta = res.position_in_rect.x - res.last_position_in_rect.x;
-          let delta_ratio = delta / (ctx.measure.size.x - handle_size.x);
-          (self.value + delta_ratio).clamp(0., 1.)
+    //Tick marks, placed at the centers of the handle positions
+    //Skipped if they would be too dense to be distinguishable
+    if let (Some(tick), Some(interval)) = (&self.tick, self.tick_interval.or(self.step)) {
+      let count = ((self.max - self.min) / interval).floor();
+      if interval > 0. && count.is_finite() && travel / count >= self.tick_size.0 * 2. {
+        for idx in 0..=(count as usize) {
+          let center = travel * self.to_ratio(self.min + idx as f32 * interval) + handle_length / 2.;
+          tick.draw(ctx.paint_target, self.part_rect(rect, center - self.tick_size.0 / 2., self.tick_size.0, self.tick_size.1));
         }
-      };
-      if let Some(signal) = &self.on_change {
-        signal.fire(ctx.signal, new_value);
       }
-      //TODO call signal with new value
+    }
+
+    // The handle(s)
+    if (self.handle_size.0 > 0. && self.handle_size.1 > 0.) {
+      for &value in &values[2 - handle_count..] {
+        self.handle.draw(ctx.paint_target, self.part_rect(rect, travel * self.to_ratio(value), handle_length, self.handle_size.1));
+      }
     }
   }
 }
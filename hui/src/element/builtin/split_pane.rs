@@ -0,0 +1,301 @@
+//! a container element with two panes separated by a draggable divider
+
+use core::hash::Hash;
+use alloc::boxed::Box;
+use derive_setters::Setters;
+use glam::{vec2, Vec2};
+use hui_painter::paint::command::{PaintClip, PaintList};
+use crate::{
+  element::{MeasureContext, ProcessContext, UiElement},
+  frame::{Frame, RectFrame},
+  input::MouseButton,
+  layout::{compute_size, Direction, LayoutInfo, Size, Size2d},
+  measure::Response,
+  rect::Rect,
+  signal::{trigger::SignalTriggerArg, Signal},
+  state::{state_id, State, StateId},
+};
+
+/// State of a [`SplitPane`] element
+#[derive(Clone, Copy, Debug, Default)]
+pub struct SplitPaneState {
+  /// Ratio of the available space taken by the first pane, in range `0.0..=1.0`
+  pub ratio: f32,
+
+  /// Length of the first pane at the time the divider started being dragged
+  pub drag_start: Option<f32>,
+}
+
+impl State for SplitPaneState {}
+
+struct SplitPaneUserData {
+  /// Length of the first pane along the primary axis
+  first_length: f32,
+  first: Option<Response>,
+  second: Option<Response>,
+}
+
+/// A container element with two panes separated by a draggable divider
+///
+/// With [`Direction::Horizontal`] the panes are placed side by side, with [`Direction::Vertical`] one above the other\
+/// Each pane is clipped to its own rect, and split panes can be nested to create more complex layouts
+///
+/// The split ratio is stored in the [`StateRepo`](crate::state::StateRepo) under the given id
+#[derive(Setters)]
+#[setters(no_std, prefix = "with_")]
+pub struct SplitPane {
+  /// Id used to store the state of the element
+  #[setters(skip)]
+  pub id: StateId,
+
+  /// Element displayed in the first (left or top) pane
+  #[setters(skip)]
+  pub first: Option<Box<dyn UiElement>>,
+
+  /// Element displayed in the second (right or bottom) pane
+  #[setters(skip)]
+  pub second: Option<Box<dyn UiElement>>,
+
+  /// Size of the element
+  #[setters(into)]
+  pub size: Size2d,
+
+  /// Direction in which the panes are placed
+  pub direction: Direction,
+
+  /// Initial ratio of the available space taken by the first pane, used until the divider gets dragged
+  pub default_ratio: f32,
+
+  /// Minimum length of the first pane, in pixels
+  pub min_first: f32,
+
+  /// Minimum length of the second pane, in pixels
+  pub min_second: f32,
+
+  /// Thickness of the divider, in pixels
+  pub divider_width: f32,
+
+  /// Divider frame
+  #[setters(skip)]
+  pub divider: Box<dyn Frame>,
+
+  /// Divider frame used while hovered or dragged
+  #[setters(skip)]
+  pub divider_active: Box<dyn Frame>,
+
+  /// Signal that will be fired with the new ratio while the divider is being dragged
+  #[setters(skip)]
+  pub on_change: Option<SignalTriggerArg<f32>>,
+}
+
+impl SplitPane {
+  pub fn new(id: impl Hash) -> Self {
+    Self {
+      id: state_id(id),
+      first: None,
+      second: None,
+      size: (Size::Relative(1.), Size::Relative(1.)).into(),
+      direction: Direction::Horizontal,
+      default_ratio: 0.5,
+      min_first: 0.,
+      min_second: 0.,
+      divider_width: 4.,
+      divider: Box::new(RectFrame::color((0.2, 0.2, 0.2))),
+      divider_active: Box::new(RectFrame::color((0.0, 0.47, 0.8))),
+      on_change: None,
+    }
+  }
+
+  pub fn with_first(mut self, element: impl UiElement + 'static) -> Self {
+    self.first = Some(Box::new(element));
+    self
+  }
+
+  pub fn with_second(mut self, element: impl UiElement + 'static) -> Self {
+    self.second = Some(Box::new(element));
+    self
+  }
+
+  pub fn with_divider(mut self, frame: impl Frame + 'static) -> Self {
+    self.divider = Box::new(frame);
+    self
+  }
+
+  pub fn with_divider_active(mut self, frame: impl Frame + 'static) -> Self {
+    self.divider_active = Box::new(frame);
+    self
+  }
+
+  pub fn on_change<S: Signal, T: Fn(f32) -> S + 'static>(self, f: T) -> Self {
+    Self {
+      on_change: Some(SignalTriggerArg::new(f)),
+      ..self
+    }
+  }
+
+  /// Get the component of the vector along the primary axis
+  fn primary(&self, v: Vec2) -> f32 {
+    match self.direction {
+      Direction::Horizontal => v.x,
+      Direction::Vertical => v.y,
+    }
+  }
+
+  /// Create a vector from the primary and secondary axis components
+  fn join(&self, primary: f32, secondary: f32) -> Vec2 {
+    match self.direction {
+      Direction::Horizontal => vec2(primary, secondary),
+      Direction::Vertical => vec2(secondary, primary),
+    }
+  }
+
+  /// Space available to the panes along the primary axis
+  fn available(&self, size: Vec2) -> f32 {
+    (self.primary(size) - self.divider_width).max(0.)
+  }
+
+  /// Compute the length of the first pane, respecting the minimum sizes\
+  /// If both minimums can't be satisfied at once, the first pane takes priority
+  fn first_length(&self, available: f32, ratio: f32) -> f32 {
+    (ratio * available)
+      .min(available - self.min_second)
+      .max(self.min_first.min(available))
+  }
+
+  /// Rects of the first pane, the divider and the second pane
+  fn rects(&self, rect: Rect, first_length: f32) -> (Rect, Rect, Rect) {
+    let secondary = match self.direction {
+      Direction::Horizontal => rect.size.y,
+      Direction::Vertical => rect.size.x,
+    };
+    let second_length = self.available(rect.size) - first_length;
+    let divider_offset = self.join(first_length, 0.);
+    let second_offset = self.join(first_length + self.divider_width, 0.);
+    (
+      Rect::new(rect.position, self.join(first_length, secondary)),
+      Rect::new(rect.position + divider_offset, self.join(self.divider_width, secondary)),
+      Rect::new(rect.position + second_offset, self.join(second_length, secondary)),
+    )
+  }
+}
+
+impl UiElement for SplitPane {
+  fn name(&self) -> &'static str {
+    "split_pane"
+  }
+
+  fn size(&self) -> Option<Size2d> {
+    Some(self.size)
+  }
+
+  fn measure(&self, ctx: MeasureContext) -> Response {
+    let size = compute_size(ctx.layout, self.size, ctx.layout.max_size);
+    let ratio = ctx.state.peek::<SplitPaneState>(self.id)
+      .map_or(self.default_ratio, |state| state.ratio);
+    let first_length = self.first_length(self.available(size), ratio);
+    let (first_rect, _, second_rect) = self.rects(Rect::new(ctx.layout.position, size), first_length);
+    let measure_pane = |element: &Option<Box<dyn UiElement>>, rect: Rect| {
+      element.as_ref().map(|element| element.measure(MeasureContext {
+        painter: ctx.painter,
+        layout: &LayoutInfo {
+          position: rect.position,
+          max_size: rect.size,
+          direction: Direction::Vertical,
+          remaining_space: None,
+        },
+        state: ctx.state,
+        current_font: ctx.current_font,
+      }))
+    };
+    Response {
+      size,
+      user_data: Some(Box::new(SplitPaneUserData {
+        first_length,
+        first: measure_pane(&self.first, first_rect),
+        second: measure_pane(&self.second, second_rect),
+      })),
+      ..Default::default()
+    }
+  }
+
+  fn process(&self, ctx: ProcessContext) {
+    let user_data: &SplitPaneUserData = ctx.measure.user_data
+      .as_ref().expect("no user data attached to split pane")
+      .downcast_ref().expect("invalid user data type");
+
+    let rect = ctx.measure.rect(ctx.layout.position);
+    let available = self.available(rect.size);
+    let (first_rect, divider_rect, second_rect) = self.rects(rect, user_data.first_length);
+
+    //Divider dragging
+    //The new ratio is applied during the next frame, as the panes are already measured
+    let state = ctx.state.acquire_mut_or_insert(self.id, SplitPaneState {
+      ratio: self.default_ratio,
+      drag_start: None,
+    });
+    if ctx.input.mouse_button_just_pressed(MouseButton::Primary) && ctx.input.check_hover(divider_rect) {
+      state.drag_start = Some(user_data.first_length);
+    }
+    let dragging = match (state.drag_start, ctx.input.check_active(divider_rect)) {
+      (Some(start), Some(res)) => {
+        let delta = self.primary(res.position_in_rect - res.start_position_in_rect);
+        let ratio = match available > 0. {
+          true => self.first_length(available, (start + delta) / available) / available,
+          false => self.default_ratio,
+        };
+        if ratio != state.ratio {
+          state.ratio = ratio;
+          if let Some(signal) = &self.on_change {
+            signal.fire(ctx.signal, ratio);
+          }
+        }
+        true
+      },
+      _ => {
+        state.drag_start = None;
+        false
+      },
+    };
+
+    //Process the panes, clipping each one to its own rect
+    for (element, measure, pane_rect) in [
+      (&self.first, &user_data.first, first_rect),
+      (&self.second, &user_data.second, second_rect),
+    ] {
+      let (Some(element), Some(measure)) = (element, measure) else {
+        continue
+      };
+      let mut sub_list = PaintList::new_empty();
+      element.process(ProcessContext {
+        painter: ctx.painter,
+        measure,
+        layout: &LayoutInfo {
+          position: pane_rect.position,
+          max_size: pane_rect.size,
+          direction: Direction::Vertical,
+          remaining_space: None,
+        },
+        paint_target: &mut sub_list,
+        paint_overlay: ctx.paint_overlay,
+        state: ctx.state,
+        current_font: ctx.current_font,
+        input: ctx.input.with_clip(pane_rect),
+        signal: ctx.signal,
+        time: ctx.time,
+        root_rect: ctx.root_rect,
+        overlay_occluders: ctx.overlay_occluders,
+      });
+      ctx.paint_target.add(PaintClip {
+        rect: pane_rect,
+        child: sub_list,
+      });
+    }
+
+    //Draw the divider
+    if dragging || ctx.input.check_hover(divider_rect) {
+      self.divider_active.draw(ctx.paint_target, divider_rect);
+    } else {
+      self.divider.draw(ctx.paint_target, divider_rect);
+    }
+  }
+}
@@ -0,0 +1,304 @@
+//! a tabbed container element, with a header row of tabs and a single visible page
+
+use core::hash::Hash;
+use alloc::{borrow::Cow, boxed::Box, vec::Vec};
+use derive_setters::Setters;
+use glam::{vec2, Affine2, Vec4};
+use hui_painter::{
+  paint::command::{text::PaintText, PaintCommand, PaintTransform},
+  text::FontHandle,
+};
+use crate::{
+  element::{container::Container, ElementList, MeasureContext, ProcessContext, UiElement},
+  frame::{Frame, RectFrame},
+  layout::{compute_size, Direction, LayoutInfo, Size, Size2d},
+  measure::Response,
+  rect::{Rect, Sides},
+  signal::{trigger::SignalTriggerArg, Signal},
+  state::{state_id, State, StateId},
+};
+
+/// State of a [`Tabs`] element
+#[derive(Clone, Copy, Debug, Default)]
+pub struct TabsState {
+  /// Index of the currently selected tab
+  pub selected: usize,
+}
+
+impl State for TabsState {}
+
+/// Callback building the contents of the page with the given index
+pub type TabContentFn = dyn Fn(usize, &mut ElementList);
+
+struct TabsUserData {
+  /// Width of each tab in the header
+  tab_widths: Vec<f32>,
+  /// Height of the header row
+  header_height: f32,
+  /// Container holding the elements of the active page
+  page: Container,
+  /// Measure response of the page container
+  page_measure: Response,
+}
+
+/// A tabbed container element
+///
+/// Renders a header row with a tab for each label, and the contents of the active page below it\
+/// The selected tab is stored in the [`StateRepo`](crate::state::StateRepo) under the given id
+///
+/// The page contents are built by the `content` callback, which is only called for the active page
+/// (possibly multiple times per frame, so it should be cheap)
+#[derive(Setters)]
+#[setters(no_std, prefix = "with_")]
+pub struct Tabs {
+  /// Id used to store the state of the element
+  #[setters(skip)]
+  pub id: StateId,
+
+  /// Labels of the tabs
+  pub tabs: Vec<Cow<'static, str>>,
+
+  /// Index of the tab selected initially (until the user selects a different one)
+  pub default_tab: usize,
+
+  /// Size of the element
+  #[setters(into)]
+  pub size: Size2d,
+
+  /// Padding inside each of the tabs
+  #[setters(into)]
+  pub tab_padding: Sides<f32>,
+
+  /// Gap between the tabs
+  pub tab_gap: f32,
+
+  /// Padding inside the page (distance from the edges to the page elements)
+  #[setters(into)]
+  pub page_padding: Sides<f32>,
+
+  /// Color of the tab labels
+  #[setters(into)]
+  pub color: Vec4,
+
+  /// Font to use for rendering the tab labels\
+  /// If set to `None` either currently selected font or the default font will be used
+  #[setters(into)]
+  pub font: Option<FontHandle>,
+
+  /// Size of the tab labels, in points (these are not pixels)
+  pub text_size: f32,
+
+  /// Frame used for the inactive tabs
+  #[setters(skip)]
+  pub tab_frame: Box<dyn Frame>,
+
+  /// Frame used for the inactive tabs while the mouse pointer is over them
+  #[setters(skip)]
+  pub tab_frame_hovered: Box<dyn Frame>,
+
+  /// Frame used for the active tab
+  #[setters(skip)]
+  pub tab_frame_active: Box<dyn Frame>,
+
+  /// Background frame of the page
+  #[setters(skip)]
+  pub page_frame: Box<dyn Frame>,
+
+  /// Callback that builds the contents of the page with the given index
+  #[setters(skip)]
+  pub content: Box<TabContentFn>,
+
+  /// Signal that will be fired with the index of the tab when it gets selected
+  #[setters(skip)]
+  pub on_change: Option<SignalTriggerArg<usize>>,
+}
+
+impl Tabs {
+  pub fn new(id: impl Hash) -> Self {
+    Self {
+      id: state_id(id),
+      tabs: Vec::new(),
+      default_tab: 0,
+      size: (Size::Auto, Size::Auto).into(),
+      tab_padding: Sides::horizontal_vertical(10., 5.),
+      tab_gap: 2.,
+      page_padding: Sides::all(5.),
+      color: Vec4::new(1., 1., 1., 1.),
+      font: None,
+      text_size: 16.,
+      tab_frame: Box::new(RectFrame::color((0.15, 0.15, 0.15))),
+      tab_frame_hovered: Box::new(RectFrame::color((0.25, 0.25, 0.25))),
+      tab_frame_active: Box::new(RectFrame::color((0.3, 0.3, 0.3))),
+      page_frame: Box::new(RectFrame::color((0.3, 0.3, 0.3))),
+      content: Box::new(|_, _| ()),
+      on_change: None,
+    }
+  }
+
+  /// Set the callback that builds the contents of the page with the given index
+  pub fn with_content(mut self, content: impl Fn(usize, &mut ElementList) + 'static) -> Self {
+    self.content = Box::new(content);
+    self
+  }
+
+  pub fn on_change<S: Signal, T: Fn(usize) -> S + 'static>(self, f: T) -> Self {
+    Self {
+      on_change: Some(SignalTriggerArg::new(f)),
+      ..self
+    }
+  }
+
+  pub fn with_tab_frame(mut self, frame: impl Frame + 'static) -> Self {
+    self.tab_frame = Box::new(frame);
+    self
+  }
+
+  pub fn with_tab_frame_hovered(mut self, frame: impl Frame + 'static) -> Self {
+    self.tab_frame_hovered = Box::new(frame);
+    self
+  }
+
+  pub fn with_tab_frame_active(mut self, frame: impl Frame + 'static) -> Self {
+    self.tab_frame_active = Box::new(frame);
+    self
+  }
+
+  pub fn with_page_frame(mut self, frame: impl Frame + 'static) -> Self {
+    self.page_frame = Box::new(frame);
+    self
+  }
+
+  /// Get the index of the selected tab, kept in range in case the list of tabs has changed
+  fn selected(&self, state: Option<&TabsState>) -> usize {
+    state
+      .map_or(self.default_tab, |state| state.selected)
+      .min(self.tabs.len().saturating_sub(1))
+  }
+
+  fn paint_text(&self, text: Cow<'static, str>, current_font: FontHandle) -> PaintText {
+    PaintText::new(text, self.font.unwrap_or(current_font), self.text_size, self.color)
+  }
+}
+
+impl UiElement for Tabs {
+  fn name(&self) -> &'static str {
+    "tabs"
+  }
+
+  fn size(&self) -> Option<Size2d> {
+    Some(self.size)
+  }
+
+  fn measure(&self, ctx: MeasureContext) -> Response {
+    //Header row
+    let line_height = self.paint_text("".into(), ctx.current_font).line_height(ctx.painter);
+    let header_height = line_height + self.tab_padding.sum_vertical();
+    let tab_widths: Vec<f32> = self.tabs.iter().map(|label| {
+      self.paint_text(label.clone(), ctx.current_font).bounds(ctx.painter).size.x + self.tab_padding.sum_horizontal()
+    }).collect();
+    let header_width = tab_widths.iter().sum::<f32>() + self.tab_gap * tab_widths.len().saturating_sub(1) as f32;
+
+    //Build and measure the active page
+    //The page fills the rest of the element, unless it's size is set to auto
+    let selected = self.selected(ctx.state.peek::<TabsState>(self.id));
+    let page = Container::default()
+      .with_size((
+        if self.size.width == Size::Auto { Size::Auto } else { Size::Relative(1.) },
+        if self.size.height == Size::Auto { Size::Auto } else { Size::Relative(1.) },
+      ))
+      .with_padding(self.page_padding)
+      .with_children(|ui| if !self.tabs.is_empty() {
+        (self.content)(selected, ui)
+      });
+    let max_size = compute_size(ctx.layout, self.size, ctx.layout.max_size);
+    let page_measure = page.measure(MeasureContext {
+      painter: ctx.painter,
+      layout: &LayoutInfo {
+        position: ctx.layout.position,
+        max_size: vec2(max_size.x, (max_size.y - header_height).max(0.)),
+        direction: Direction::Vertical,
+        remaining_space: None,
+      },
+      state: ctx.state,
+      current_font: ctx.current_font,
+    });
+
+    Response {
+      size: compute_size(ctx.layout, self.size, vec2(
+        header_width.max(page_measure.size.x),
+        header_height + page_measure.size.y,
+      )),
+      user_data: Some(Box::new(TabsUserData {
+        tab_widths,
+        header_height,
+        page,
+        page_measure,
+      })),
+      ..Default::default()
+    }
+  }
+
+  fn process(&self, ctx: ProcessContext) {
+    let user_data: &TabsUserData = ctx.measure.user_data
+      .as_ref().expect("no user data attached to tabs")
+      .downcast_ref().expect("invalid user data type");
+
+    let selected = self.selected(ctx.state.peek::<TabsState>(self.id));
+    let state = ctx.state.acquire_mut_or_default::<TabsState>(self.id);
+    state.selected = selected;
+
+    //Draw the header row and handle tab selection
+    let line_height = self.paint_text("".into(), ctx.current_font).line_height(ctx.painter);
+    let mut position = ctx.layout.position;
+    for (idx, (label, &width)) in self.tabs.iter().zip(&user_data.tab_widths).enumerate() {
+      let rect = Rect::new(position, vec2(width, user_data.header_height));
+      let frame = if state.selected == idx {
+        &self.tab_frame_active
+      } else if ctx.input.check_hover(rect) {
+        &self.tab_frame_hovered
+      } else {
+        &self.tab_frame
+      };
+      frame.draw(ctx.paint_target, rect);
+      if self.color.w > 0. {
+        ctx.paint_target.add(PaintTransform {
+          transform: Affine2::from_translation(position + vec2(
+            self.tab_padding.left,
+            ((user_data.header_height - line_height) / 2.).round(),
+          )),
+          child: self.paint_text(label.clone(), ctx.current_font),
+        });
+      }
+      if state.selected != idx && ctx.input.check_click(rect).is_some() {
+        state.selected = idx;
+        if let Some(signal) = &self.on_change {
+          signal.fire(ctx.signal, idx);
+        }
+      }
+      position.x += width + self.tab_gap;
+    }
+
+    //Draw the page background and process the active page
+    let page_position = ctx.layout.position + vec2(0., user_data.header_height);
+    self.page_frame.draw(ctx.paint_target, Rect::new(page_position, user_data.page_measure.size));
+    user_data.page.process(ProcessContext {
+      painter: ctx.painter,
+      measure: &user_data.page_measure,
+      layout: &LayoutInfo {
+        position: page_position,
+        max_size: vec2(ctx.measure.size.x, ctx.measure.size.y - user_data.header_height),
+        direction: Direction::Vertical,
+        remaining_space: None,
+      },
+      paint_target: ctx.paint_target,
+      paint_overlay: ctx.paint_overlay,
+      state: ctx.state,
+      current_font: ctx.current_font,
+      input: ctx.input,
+      signal: ctx.signal,
+      time: ctx.time,
+      root_rect: ctx.root_rect,
+      overlay_occluders: ctx.overlay_occluders,
+    });
+  }
+}
@@ -0,0 +1,445 @@
+//! multi-line text editing element
+
+use core::hash::Hash;
+use alloc::{borrow::Cow, boxed::Box, string::String, vec::Vec};
+use derive_setters::Setters;
+use glam::{vec2, Affine2, Vec4};
+use hui_painter::{
+  paint::command::{text::PaintText, PaintTransform},
+  text::FontHandle,
+};
+use crate::{
+  element::{
+    text_input::{byte_index, closest_caret, TextInputState},
+    MeasureContext, ProcessContext, UiElement,
+  },
+  event::UiEvent,
+  frame::{Frame, RectFrame},
+  input::{ButtonState, KeyboardKey, MouseButton},
+  layout::{compute_size, Size, Size2d},
+  measure::Response,
+  rect::{Rect, Sides},
+  signal::{trigger::SignalTriggerArg, Signal},
+  state::{state_id, StateId},
+};
+
+/// A single visual line of text
+///
+/// `start..end` is the range of characters displayed on the line (excluding the line break)
+#[derive(Clone, Copy, Debug)]
+struct VisualLine {
+  start: usize,
+  end: usize,
+}
+
+/// Split the text into visual lines, wrapping them at word boundaries to fit the given width
+///
+/// `offsets` must be the caret offsets of the text, as returned by [`PaintText::caret_offsets`]
+fn wrap_lines(text: &str, offsets: &[f32], max_width: f32) -> Vec<VisualLine> {
+  let chars: Vec<char> = text.chars().collect();
+  let mut lines = Vec::new();
+  let mut start = 0;
+  let mut last_space = None;
+  let mut idx = 0;
+  while idx < chars.len() {
+    let c = chars[idx];
+    if c == '\n' {
+      lines.push(VisualLine { start, end: idx });
+      start = idx + 1;
+      last_space = None;
+      idx += 1;
+      continue
+    }
+    if idx > start && offsets[idx + 1] - offsets[start] > max_width {
+      //Prefer breaking after the last space, otherwise break in the middle of the word
+      let end = match last_space {
+        Some(space_idx) => space_idx + 1,
+        None => idx,
+      };
+      lines.push(VisualLine { start, end });
+      start = end;
+      last_space = None;
+      continue
+    }
+    if c == ' ' {
+      last_space = Some(idx);
+    }
+    idx += 1;
+  }
+  lines.push(VisualLine { start, end: chars.len() });
+  lines
+}
+
+/// Get the index of the visual line containing the caret
+fn line_of(lines: &[VisualLine], caret: usize) -> usize {
+  lines.iter()
+    .rposition(|line| line.start <= caret)
+    .unwrap_or(0)
+}
+
+/// Get the last caret position on the given line
+fn line_end(lines: &[VisualLine], line_idx: usize) -> usize {
+  let line = lines[line_idx];
+  //If the line was soft-wrapped, the caret at its end would be displayed on the next line
+  let soft_wrapped = lines.get(line_idx + 1).is_some_and(|next| next.start == line.end);
+  if soft_wrapped { line.end.saturating_sub(1).max(line.start) } else { line.end }
+}
+
+/// Get the caret position closest to the given x offset on the given line
+fn caret_on_line(lines: &[VisualLine], offsets: &[f32], line_idx: usize, x: f32) -> usize {
+  let start = lines[line_idx].start;
+  let line_offsets = &offsets[start..=line_end(lines, line_idx)];
+  start + closest_caret(line_offsets, x + offsets[start])
+}
+
+/// Multi-line text editing element
+///
+/// Lines are wrapped to fit the width of the element,\
+/// and the contents are scrolled vertically once they no longer fit
+///
+/// Uses the same state as [`TextInput`](crate::element::text_input::TextInput),
+/// the contents are stored in the [`StateRepo`](crate::state::StateRepo) under the given id\
+/// While the element is not focused, the contents are synchronized with the [`TextArea::value`],\
+/// so you should update it in response to the `on_change` signal
+#[derive(Setters)]
+#[setters(no_std, prefix = "with_")]
+pub struct TextArea {
+  /// Id used to store the state of the element
+  #[setters(skip)]
+  pub id: StateId,
+
+  /// Current value of the text area
+  #[setters(into)]
+  pub value: Cow<'static, str>,
+
+  /// Size of the element
+  #[setters(into)]
+  pub size: Size2d,
+
+  /// Number of visible lines, used if the height is set to `Size::Auto`
+  pub rows: usize,
+
+  /// Padding inside the element (distance from the edges to the text)
+  #[setters(into)]
+  pub padding: Sides<f32>,
+
+  /// Color of the text
+  #[setters(into)]
+  pub color: Vec4,
+
+  /// Font to use for rendering the text\
+  /// If set to `None` either currently selected font or the default font will be used
+  #[setters(into)]
+  pub font: Option<FontHandle>,
+
+  /// Size of the text, in points (these are not pixels)
+  pub text_size: f32,
+
+  /// Width of the caret, in pixels
+  pub caret_width: f32,
+
+  /// Background frame
+  #[setters(skip)]
+  pub background: Box<dyn Frame>,
+
+  /// Background frame used while the text area is focused
+  #[setters(skip)]
+  pub background_focused: Box<dyn Frame>,
+
+  /// Caret frame
+  #[setters(skip)]
+  pub caret: Box<dyn Frame>,
+
+  /// Frame drawn behind the selected text
+  #[setters(skip)]
+  pub selection: Box<dyn Frame>,
+
+  /// Signal fired whenever the contents of the text area change
+  #[setters(skip)]
+  pub on_change: Option<SignalTriggerArg<String>>,
+}
+
+impl TextArea {
+  pub const DEFAULT_ROWS: usize = 5;
+
+  pub fn new(id: impl Hash) -> Self {
+    Self {
+      id: state_id(id),
+      value: "".into(),
+      size: (Size::Auto, Size::Auto).into(),
+      rows: Self::DEFAULT_ROWS,
+      padding: Sides::all(5.),
+      color: Vec4::new(1., 1., 1., 1.),
+      font: None,
+      text_size: 16.,
+      caret_width: 1.,
+      background: Box::new(RectFrame::color((0.1, 0.1, 0.1))),
+      background_focused: Box::new(RectFrame::color((0.15, 0.15, 0.15))),
+      caret: Box::new(RectFrame::color((1., 1., 1.))),
+      selection: Box::new(RectFrame::color((0.2, 0.4, 0.8, 0.5))),
+      on_change: None,
+    }
+  }
+
+  pub fn on_change<S: Signal, F: Fn(String) -> S + 'static>(self, f: F) -> Self {
+    Self {
+      on_change: Some(SignalTriggerArg::new(f)),
+      ..self
+    }
+  }
+
+  pub fn with_background(mut self, frame: impl Frame + 'static) -> Self {
+    self.background = Box::new(frame);
+    self
+  }
+
+  pub fn with_background_focused(mut self, frame: impl Frame + 'static) -> Self {
+    self.background_focused = Box::new(frame);
+    self
+  }
+
+  pub fn with_caret(mut self, frame: impl Frame + 'static) -> Self {
+    self.caret = Box::new(frame);
+    self
+  }
+
+  pub fn with_selection(mut self, frame: impl Frame + 'static) -> Self {
+    self.selection = Box::new(frame);
+    self
+  }
+
+  fn paint_text(&self, text: impl Into<Cow<'static, str>>, current_font: FontHandle) -> PaintText {
+    PaintText::new(text, self.font.unwrap_or(current_font), self.text_size, self.color)
+  }
+}
+
+impl UiElement for TextArea {
+  fn name(&self) -> &'static str {
+    "text_area"
+  }
+
+  fn size(&self) -> Option<Size2d> {
+    Some(self.size)
+  }
+
+  fn measure(&self, ctx: MeasureContext) -> Response {
+    let line_height = self.paint_text("", ctx.current_font).line_height(ctx.painter);
+    Response {
+      size: compute_size(ctx.layout, self.size, vec2(
+        ctx.layout.max_size.x,
+        line_height * self.rows as f32 + self.padding.sum_vertical(),
+      )),
+      ..Default::default()
+    }
+  }
+
+  fn process(&self, ctx: ProcessContext) {
+    let rect = ctx.measure.rect(ctx.layout.position);
+    let inner = Rect {
+      position: rect.position + self.padding.top_left(),
+      size: rect.size - self.padding.sum_vec(),
+    };
+    let line_height = self.paint_text("", ctx.current_font).line_height(ctx.painter);
+    let visible_lines = ((inner.size.y / line_height).floor() as usize).max(1);
+
+    let state = ctx.state.acquire_mut_or_default::<TextInputState>(self.id);
+    state.update_focus(&ctx.input, rect, &self.value);
+
+    //Compute the layout of the text before handling the input
+    //It's recomputed after every edit, since the line navigation depends on it
+    let layout = |state: &TextInputState, painter| {
+      let offsets = self.paint_text(state.buffer.clone(), ctx.current_font).caret_offsets(painter);
+      let lines = wrap_lines(&state.buffer, &offsets, inner.size.x);
+      (offsets, lines)
+    };
+    let (mut offsets, mut lines) = layout(state, &*ctx.painter);
+
+    let mut changed = false;
+    if state.focused {
+      for event in ctx.input.events() {
+        let mut edited = false;
+        match *event {
+          UiEvent::KeyboardButton { key, state: ButtonState::Pressed } => {
+            let line_delta = match key {
+              KeyboardKey::Up => Some(-1),
+              KeyboardKey::Down => Some(1),
+              KeyboardKey::PageUp => Some(-(visible_lines as isize)),
+              KeyboardKey::PageDown => Some(visible_lines as isize),
+              _ => None,
+            };
+            if let Some(line_delta) = line_delta {
+              let line_idx = line_of(&lines, state.caret);
+              let x = offsets[state.caret] - offsets[lines[line_idx].start];
+              let target_line = line_idx.saturating_add_signed(line_delta).min(lines.len() - 1);
+              let target = if target_line == line_idx {
+                //Already at the first/last line, move to the beginning/end of the text
+                if line_delta < 0 { 0 } else { state.len() }
+              } else {
+                caret_on_line(&lines, &offsets, target_line, x)
+              };
+              state.move_caret(target, ctx.input.shift_down());
+            } else if (key == KeyboardKey::Home || key == KeyboardKey::End) && !ctx.input.control_down() {
+              //Home/End move to the start/end of the current visual line (Ctrl+Home/End move to the start/end of the text)
+              let line_idx = line_of(&lines, state.caret);
+              let target = match key {
+                KeyboardKey::Home => lines[line_idx].start,
+                _ => line_end(&lines, line_idx),
+              };
+              state.move_caret(target, ctx.input.shift_down());
+            } else if key == KeyboardKey::Enter || key == KeyboardKey::NpEnter {
+              state.insert_char('\n');
+              edited = true;
+            } else {
+              edited = state.handle_key(key, &ctx.input);
+            }
+          },
+          UiEvent::TextInput(c) if !c.is_control() => {
+            state.insert_char(c);
+            edited = true;
+          },
+          _ => (),
+        }
+        if edited {
+          changed = true;
+          (offsets, lines) = layout(state, &*ctx.painter);
+        }
+      }
+    }
+
+    //Caret placement and selection with the mouse pointer
+    if state.focused {
+      if let Some(res) = ctx.input.check_active(rect) {
+        let position = res.position_in_rect - self.padding.top_left() + vec2(0., state.scroll);
+        let line_idx = ((position.y / line_height).floor().max(0.) as usize).min(lines.len() - 1);
+        let index = caret_on_line(&lines, &offsets, line_idx, position.x);
+        if ctx.input.mouse_button_just_pressed(MouseButton::Primary) {
+          state.caret = index;
+        } else {
+          state.move_caret(index, true);
+        }
+      }
+    }
+
+    //Scroll to keep the line containing the caret visible
+    let caret_line = line_of(&lines, state.caret);
+    let caret_y = caret_line as f32 * line_height;
+    if caret_y + line_height - state.scroll > inner.size.y {
+      state.scroll = caret_y + line_height - visible_lines as f32 * line_height;
+    }
+    if caret_y < state.scroll {
+      state.scroll = caret_y;
+    }
+    let max_scroll = lines.len().saturating_sub(visible_lines) as f32 * line_height;
+    state.scroll = state.scroll.min(max_scroll).max(0.);
+
+    if changed {
+      if let Some(signal) = &self.on_change {
+        signal.fire(ctx.signal, state.buffer.clone());
+      }
+    }
+
+    //Draw the background
+    if state.focused {
+      self.background_focused.draw(ctx.paint_target, rect);
+    } else {
+      self.background.draw(ctx.paint_target, rect);
+    }
+
+    //Draw the visible lines
+    //Only the lines that fully fit inside the element are drawn
+    let first_line = (state.scroll / line_height).round() as usize;
+    let selection = state.selection().filter(|_| state.focused);
+    for (line_idx, line) in lines.iter().enumerate().skip(first_line).take(visible_lines) {
+      let position = inner.position + vec2(0., (line_idx - first_line) as f32 * line_height);
+
+      if let Some(range) = &selection {
+        let start = range.start.max(line.start);
+        let end = range.end.min(line.end);
+        if start < end || (start == end && range.contains(&line.end) && line.start <= start) {
+          let start_x = offsets[start] - offsets[line.start];
+          //Extend the selection slightly if it spans over the line break
+          let end_x = offsets[end] - offsets[line.start] + if range.end > line.end { self.text_size / 4. } else { 0. };
+          self.selection.draw(ctx.paint_target, Rect {
+            position: position + vec2(start_x, 0.),
+            size: vec2((end_x - start_x).min(inner.size.x - start_x), line_height),
+          });
+        }
+      }
+
+      if line.start < line.end && self.color.w > 0. {
+        let text = &state.buffer[byte_index(&state.buffer, line.start)..byte_index(&state.buffer, line.end)];
+        ctx.paint_target.add(PaintTransform {
+          transform: Affine2::from_translation(position),
+          child: self.paint_text(String::from(text.trim_end_matches(' ')), ctx.current_font),
+        });
+      }
+    }
+
+    //Draw the caret
+    if state.focused && self.caret_width > 0. && (first_line..first_line + visible_lines).contains(&caret_line) {
+      let caret_x = offsets[state.caret] - offsets[lines[caret_line].start];
+      self.caret.draw(ctx.paint_target, Rect {
+        position: inner.position + vec2(
+          caret_x.min(inner.size.x - self.caret_width),
+          (caret_line - first_line) as f32 * line_height,
+        ),
+        size: vec2(self.caret_width, line_height),
+      });
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  /// Wrap the text, assuming all characters are 10 pixels wide
+  fn wrap(text: &str, max_width: f32) -> Vec<(usize, usize)> {
+    let offsets: Vec<f32> = (0..=text.chars().count()).map(|idx| idx as f32 * 10.).collect();
+    wrap_lines(text, &offsets, max_width).iter().map(|line| (line.start, line.end)).collect()
+  }
+
+  #[test]
+  fn test_wrap_lines_empty() {
+    assert_eq!(wrap("", 100.), [(0, 0)]);
+  }
+
+  #[test]
+  fn test_wrap_lines_fits() {
+    assert_eq!(wrap("hello world", 200.), [(0, 11)]);
+  }
+
+  #[test]
+  fn test_wrap_lines_hard_breaks() {
+    assert_eq!(wrap("ab\ncd", 200.), [(0, 2), (3, 5)]);
+    assert_eq!(wrap("ab\n", 200.), [(0, 2), (3, 3)]);
+    assert_eq!(wrap("\n\n", 200.), [(0, 0), (1, 1), (2, 2)]);
+  }
+
+  #[test]
+  fn test_wrap_lines_at_word_boundary() {
+    //The space stays at the end of the first line
+    assert_eq!(wrap("hello world", 60.), [(0, 6), (6, 11)]);
+    assert_eq!(wrap("aa bb cc", 60.), [(0, 6), (6, 8)]);
+  }
+
+  #[test]
+  fn test_wrap_lines_long_word() {
+    assert_eq!(wrap("abcdefgh", 30.), [(0, 3), (3, 6), (6, 8)]);
+  }
+
+  #[test]
+  fn test_wrap_lines_narrower_than_a_character() {
+    //Every line must contain at least one character
+    assert_eq!(wrap("abc", 5.), [(0, 1), (1, 2), (2, 3)]);
+  }
+
+  #[test]
+  fn test_line_end_soft_wrapped() {
+    let offsets: Vec<f32> = (0..=11).map(|idx| idx as f32 * 10.).collect();
+    let lines = wrap_lines("hello world", &offsets, 60.);
+    assert_eq!(line_end(&lines, 0), 5);
+    assert_eq!(line_end(&lines, 1), 11);
+    assert_eq!(line_of(&lines, 6), 1);
+  }
+}
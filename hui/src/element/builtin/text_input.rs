@@ -0,0 +1,440 @@
+//! single-line text input element
+
+use core::{hash::Hash, ops::Range};
+use alloc::{borrow::Cow, boxed::Box, string::String};
+use derive_setters::Setters;
+use glam::{vec2, Affine2, Vec4};
+use hui_painter::{
+  paint::command::{text::PaintText, PaintTransform},
+  text::FontHandle,
+};
+use crate::{
+  element::{MeasureContext, ProcessContext, UiElement},
+  event::UiEvent,
+  frame::{Frame, RectFrame},
+  input::{ButtonState, InputCtx, KeyboardKey, MouseButton},
+  layout::{compute_size, Size, Size2d},
+  measure::Response,
+  rect::{Rect, Sides},
+  signal::{trigger::SignalTriggerArg, Signal},
+  state::{state_id, State, StateId},
+};
+
+/// Convert a character index into a byte index within the string
+pub(crate) fn byte_index(text: &str, char_index: usize) -> usize {
+  text.char_indices()
+    .nth(char_index)
+    .map(|(idx, _)| idx)
+    .unwrap_or(text.len())
+}
+
+/// Get the index of the caret offset closest to the given position
+pub(crate) fn closest_caret(offsets: &[f32], position: f32) -> usize {
+  offsets.iter()
+    .enumerate()
+    .min_by(|(_, a), (_, b)| (*a - position).abs().total_cmp(&(*b - position).abs()))
+    .map(|(idx, _)| idx)
+    .unwrap_or(0)
+}
+
+/// Editing state of a [`TextInput`] (or a `TextArea`) element
+///
+/// Stored in the [`StateRepo`](crate::state::StateRepo)\
+/// Caret and selection positions are specified in *characters*, not bytes
+#[derive(Default)]
+pub struct TextInputState {
+  /// Current contents of the text input
+  pub buffer: String,
+
+  /// Position of the caret
+  pub caret: usize,
+
+  /// Position where the selection started, if any\
+  /// (the selection spans between this position and the caret)
+  pub selection_anchor: Option<usize>,
+
+  /// Whether the text input has keyboard focus
+  pub focused: bool,
+
+  /// Scroll offset in pixels\
+  /// (horizontal for single-line, vertical for multi-line elements)
+  pub scroll: f32,
+}
+
+impl State for TextInputState {}
+
+impl TextInputState {
+  /// Length of the buffer in characters
+  pub fn len(&self) -> usize {
+    self.buffer.chars().count()
+  }
+
+  /// Check if the buffer is empty
+  pub fn is_empty(&self) -> bool {
+    self.buffer.is_empty()
+  }
+
+  /// Get the currently selected range of characters
+  ///
+  /// Returns `None` if nothing is selected
+  pub fn selection(&self) -> Option<Range<usize>> {
+    self.selection_anchor
+      .filter(|&anchor| anchor != self.caret)
+      .map(|anchor| anchor.min(self.caret)..anchor.max(self.caret))
+  }
+
+  /// Update the focus state in response to mouse clicks,
+  /// and synchronize the buffer with the value while not focused
+  pub(crate) fn update_focus(&mut self, input: &InputCtx, rect: Rect, value: &str) {
+    if input.mouse_button_just_pressed(MouseButton::Primary) {
+      self.focused = input.check_hover(rect);
+      self.selection_anchor = None;
+    }
+    if !self.focused && self.buffer != value {
+      self.buffer.clear();
+      self.buffer.push_str(value);
+      self.caret = self.caret.min(self.len());
+      self.selection_anchor = None;
+    }
+  }
+
+  /// Move the caret to the given position, extending the selection if `select` is set
+  pub(crate) fn move_caret(&mut self, position: usize, select: bool) {
+    if select {
+      self.selection_anchor.get_or_insert(self.caret);
+    } else {
+      self.selection_anchor = None;
+    }
+    self.caret = position.min(self.len());
+  }
+
+  /// Remove the selected text
+  ///
+  /// Returns `true` if anything was removed
+  pub(crate) fn delete_selection(&mut self) -> bool {
+    let Some(range) = self.selection() else {
+      self.selection_anchor = None;
+      return false
+    };
+    let bytes = byte_index(&self.buffer, range.start)..byte_index(&self.buffer, range.end);
+    self.buffer.replace_range(bytes, "");
+    self.caret = range.start;
+    self.selection_anchor = None;
+    true
+  }
+
+  /// Insert a character at the caret position, replacing the selected text
+  pub(crate) fn insert_char(&mut self, c: char) {
+    self.delete_selection();
+    let idx = byte_index(&self.buffer, self.caret);
+    self.buffer.insert(idx, c);
+    self.caret += 1;
+  }
+
+  /// Handle an editing key shared by all text editing elements
+  ///
+  /// Returns `true` if the contents of the buffer changed
+  pub(crate) fn handle_key(&mut self, key: KeyboardKey, input: &InputCtx) -> bool {
+    let shift = input.shift_down();
+    match key {
+      KeyboardKey::Left => {
+        let target = match self.selection() {
+          Some(range) if !shift => range.start,
+          _ => self.caret.saturating_sub(1),
+        };
+        self.move_caret(target, shift);
+      },
+      KeyboardKey::Right => {
+        let target = match self.selection() {
+          Some(range) if !shift => range.end,
+          _ => self.caret + 1,
+        };
+        self.move_caret(target, shift);
+      },
+      KeyboardKey::Home => self.move_caret(0, shift),
+      KeyboardKey::End => self.move_caret(usize::MAX, shift),
+      KeyboardKey::A if input.control_down() => {
+        self.selection_anchor = Some(0);
+        self.caret = self.len();
+      },
+      KeyboardKey::Backspace => {
+        if self.delete_selection() {
+          return true
+        }
+        if self.caret > 0 {
+          self.caret -= 1;
+          self.buffer.remove(byte_index(&self.buffer, self.caret));
+          return true
+        }
+      },
+      KeyboardKey::Delete => {
+        if self.delete_selection() {
+          return true
+        }
+        if self.caret < self.len() {
+          self.buffer.remove(byte_index(&self.buffer, self.caret));
+          return true
+        }
+      },
+      KeyboardKey::Escape => {
+        self.focused = false;
+        self.selection_anchor = None;
+      },
+      _ => (),
+    }
+    false
+  }
+}
+
+/// Single-line text input element
+///
+/// The contents of the input are stored in the [`StateRepo`](crate::state::StateRepo) under the given id\
+/// While the input is not focused, the contents are synchronized with the [`TextInput::value`],\
+/// so you should update it in response to the `on_change` signal
+#[derive(Setters)]
+#[setters(no_std, prefix = "with_")]
+pub struct TextInput {
+  /// Id used to store the state of the element
+  #[setters(skip)]
+  pub id: StateId,
+
+  /// Current value of the input
+  #[setters(into)]
+  pub value: Cow<'static, str>,
+
+  /// Text displayed while the input is empty and not focused
+  #[setters(into)]
+  pub placeholder: Cow<'static, str>,
+
+  /// Size of the element
+  #[setters(into)]
+  pub size: Size2d,
+
+  /// Padding inside the element (distance from the edges to the text)
+  #[setters(into)]
+  pub padding: Sides<f32>,
+
+  /// Color of the text
+  #[setters(into)]
+  pub color: Vec4,
+
+  /// Color of the placeholder text
+  #[setters(into)]
+  pub placeholder_color: Vec4,
+
+  /// Font to use for rendering the text\
+  /// If set to `None` either currently selected font or the default font will be used
+  #[setters(into)]
+  pub font: Option<FontHandle>,
+
+  /// Size of the text, in points (these are not pixels)
+  pub text_size: f32,
+
+  /// Width of the caret, in pixels
+  pub caret_width: f32,
+
+  /// Background frame
+  #[setters(skip)]
+  pub background: Box<dyn Frame>,
+
+  /// Background frame used while the input is focused
+  #[setters(skip)]
+  pub background_focused: Box<dyn Frame>,
+
+  /// Caret frame
+  #[setters(skip)]
+  pub caret: Box<dyn Frame>,
+
+  /// Frame drawn behind the selected text
+  #[setters(skip)]
+  pub selection: Box<dyn Frame>,
+
+  /// Signal fired whenever the contents of the input change
+  #[setters(skip)]
+  pub on_change: Option<SignalTriggerArg<String>>,
+}
+
+impl TextInput {
+  pub fn new(id: impl Hash) -> Self {
+    Self {
+      id: state_id(id),
+      value: "".into(),
+      placeholder: "".into(),
+      size: (Size::Auto, Size::Auto).into(),
+      padding: Sides::horizontal_vertical(5., 3.),
+      color: Vec4::new(1., 1., 1., 1.),
+      placeholder_color: Vec4::new(1., 1., 1., 0.5),
+      font: None,
+      text_size: 16.,
+      caret_width: 1.,
+      background: Box::new(RectFrame::color((0.1, 0.1, 0.1))),
+      background_focused: Box::new(RectFrame::color((0.15, 0.15, 0.15))),
+      caret: Box::new(RectFrame::color((1., 1., 1.))),
+      selection: Box::new(RectFrame::color((0.2, 0.4, 0.8, 0.5))),
+      on_change: None,
+    }
+  }
+
+  pub fn on_change<S: Signal, F: Fn(String) -> S + 'static>(self, f: F) -> Self {
+    Self {
+      on_change: Some(SignalTriggerArg::new(f)),
+      ..self
+    }
+  }
+
+  pub fn with_background(mut self, frame: impl Frame + 'static) -> Self {
+    self.background = Box::new(frame);
+    self
+  }
+
+  pub fn with_background_focused(mut self, frame: impl Frame + 'static) -> Self {
+    self.background_focused = Box::new(frame);
+    self
+  }
+
+  pub fn with_caret(mut self, frame: impl Frame + 'static) -> Self {
+    self.caret = Box::new(frame);
+    self
+  }
+
+  pub fn with_selection(mut self, frame: impl Frame + 'static) -> Self {
+    self.selection = Box::new(frame);
+    self
+  }
+
+  fn paint_text(&self, text: impl Into<Cow<'static, str>>, color: Vec4, current_font: FontHandle) -> PaintText {
+    PaintText::new(text, self.font.unwrap_or(current_font), self.text_size, color)
+  }
+}
+
+impl UiElement for TextInput {
+  fn name(&self) -> &'static str {
+    "text_input"
+  }
+
+  fn size(&self) -> Option<Size2d> {
+    Some(self.size)
+  }
+
+  fn measure(&self, ctx: MeasureContext) -> Response {
+    let line_height = self.paint_text("", self.color, ctx.current_font).line_height(ctx.painter);
+    Response {
+      size: compute_size(ctx.layout, self.size, vec2(
+        ctx.layout.max_size.x,
+        line_height + self.padding.sum_vertical(),
+      )),
+      ..Default::default()
+    }
+  }
+
+  fn process(&self, ctx: ProcessContext) {
+    let rect = ctx.measure.rect(ctx.layout.position);
+    let inner = Rect {
+      position: rect.position + self.padding.top_left(),
+      size: rect.size - self.padding.sum_vec(),
+    };
+
+    let state = ctx.state.acquire_mut_or_default::<TextInputState>(self.id);
+    state.update_focus(&ctx.input, rect, &self.value);
+
+    let mut changed = false;
+    if state.focused {
+      for event in ctx.input.events() {
+        match *event {
+          UiEvent::KeyboardButton { key, state: ButtonState::Pressed } => {
+            changed |= state.handle_key(key, &ctx.input);
+          },
+          UiEvent::TextInput(c) if !c.is_control() => {
+            state.insert_char(c);
+            changed = true;
+          },
+          _ => (),
+        }
+      }
+    }
+
+    let offsets = self.paint_text(state.buffer.clone(), self.color, ctx.current_font)
+      .caret_offsets(ctx.painter);
+
+    //Caret placement and selection with the mouse pointer
+    if state.focused {
+      if let Some(res) = ctx.input.check_active(rect) {
+        let position = res.position_in_rect.x - self.padding.left + state.scroll;
+        let index = closest_caret(&offsets, position);
+        if ctx.input.mouse_button_just_pressed(MouseButton::Primary) {
+          state.caret = index;
+        } else {
+          state.move_caret(index, true);
+        }
+      }
+    }
+
+    //Scroll to keep the caret visible
+    let caret_x = offsets[state.caret];
+    let text_width = *offsets.last().unwrap();
+    if caret_x - state.scroll > inner.size.x {
+      state.scroll = caret_x - inner.size.x;
+    }
+    if caret_x < state.scroll {
+      state.scroll = caret_x;
+    }
+    state.scroll = state.scroll.min(text_width - inner.size.x).max(0.);
+
+    if changed {
+      if let Some(signal) = &self.on_change {
+        signal.fire(ctx.signal, state.buffer.clone());
+      }
+    }
+
+    //Draw the background
+    if state.focused {
+      self.background_focused.draw(ctx.paint_target, rect);
+    } else {
+      self.background.draw(ctx.paint_target, rect);
+    }
+
+    //Draw the selection
+    if let (true, Some(range)) = (state.focused, state.selection()) {
+      let start = (offsets[range.start] - state.scroll).max(0.);
+      let end = (offsets[range.end] - state.scroll).min(inner.size.x);
+      self.selection.draw(ctx.paint_target, Rect {
+        position: inner.position + vec2(start, 0.),
+        size: vec2(end - start, inner.size.y),
+      });
+    }
+
+    //Draw the text
+    //Only the characters that fully fit inside the element are drawn
+    if state.buffer.is_empty() {
+      if !state.focused && !self.placeholder.is_empty() {
+        ctx.paint_target.add(PaintTransform {
+          transform: Affine2::from_translation(inner.position),
+          child: self.paint_text(self.placeholder.clone(), self.placeholder_color, ctx.current_font),
+        });
+      }
+    } else if self.color.w > 0. {
+      let visible_start = offsets.iter()
+        .position(|&x| x >= state.scroll)
+        .unwrap_or(0);
+      let visible_end = offsets.iter()
+        .rposition(|&x| x <= state.scroll + inner.size.x)
+        .unwrap_or(0)
+        .max(visible_start);
+      let visible = &state.buffer[
+        byte_index(&state.buffer, visible_start)..byte_index(&state.buffer, visible_end)
+      ];
+      ctx.paint_target.add(PaintTransform {
+        transform: Affine2::from_translation(inner.position + vec2(offsets[visible_start] - state.scroll, 0.)),
+        child: self.paint_text(String::from(visible), self.color, ctx.current_font),
+      });
+    }
+
+    //Draw the caret
+    if state.focused && self.caret_width > 0. {
+      self.caret.draw(ctx.paint_target, Rect {
+        position: inner.position + vec2((caret_x - state.scroll).min(inner.size.x - self.caret_width), 0.),
+        size: vec2(self.caret_width, inner.size.y),
+      });
+    }
+  }
+}
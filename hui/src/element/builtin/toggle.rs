@@ -0,0 +1,171 @@
+//! a toggle switch element that allows toggling a boolean value
+
+use core::hash::Hash;
+use alloc::boxed::Box;
+use derive_setters::Setters;
+use glam::vec2;
+use crate::{
+  element::{MeasureContext, ProcessContext, UiElement},
+  frame::{Frame, RectFrame},
+  layout::{compute_size, Size, Size2d},
+  measure::Response,
+  rect::Rect,
+  signal::{trigger::SignalTriggerArg, Signal},
+  state::{state_id, State, StateId},
+};
+
+/// State of a [`Toggle`] element
+#[derive(Clone, Copy, Debug, Default)]
+pub struct ToggleState {
+  /// Position of the knob, in range `0.0..=1.0` (`0.0` if off, `1.0` if on)\
+  /// Only differs from the value while the knob is being animated
+  pub progress: f32,
+}
+
+impl State for ToggleState {}
+
+/// A toggle switch element that allows toggling a boolean value
+///
+/// Consists of a track and a knob that moves to the right side of the track while the toggle is on\
+/// Clicking the toggle fires the `on_change` signal with the new (inverted) value
+///
+/// If `animation_duration` is set, the knob slides between the sides of the track\
+/// (this requires the UI clock to be updated, see [`UiInstance::advance_time`](crate::UiInstance::advance_time))
+#[derive(Setters)]
+#[setters(no_std, prefix = "with_")]
+pub struct Toggle {
+  /// Id used to store the state of the element
+  #[setters(skip)]
+  pub id: StateId,
+
+  /// Current value of the toggle
+  pub checked: bool,
+
+  /// Size of the element
+  #[setters(into)]
+  pub size: Size2d,
+
+  /// Track frame used while the toggle is off
+  #[setters(skip)]
+  pub track_unchecked: Box<dyn Frame>,
+
+  /// Track frame used while the toggle is on
+  #[setters(skip)]
+  pub track_checked: Box<dyn Frame>,
+
+  /// Knob frame
+  #[setters(skip)]
+  pub knob: Box<dyn Frame>,
+
+  /// Distance between the knob and the edges of the track, in pixels
+  pub knob_padding: f32,
+
+  /// Duration of the knob animation, in seconds\
+  /// If set to `None`, the knob moves instantly
+  #[setters(into)]
+  pub animation_duration: Option<f32>,
+
+  /// Signal that will be fired with the new value when the toggle is clicked
+  #[setters(skip)]
+  pub on_change: Option<SignalTriggerArg<bool>>,
+}
+
+impl Toggle {
+  pub const DEFAULT_SIZE: (f32, f32) = (40.0, 20.0);
+
+  pub fn new(id: impl Hash, checked: bool) -> Self {
+    Self {
+      id: state_id(id),
+      checked,
+      size: (Size::Auto, Size::Auto).into(),
+      track_unchecked: Box::new(RectFrame::color((0.25, 0.25, 0.25))),
+      track_checked: Box::new(RectFrame::color((0.0, 0.0, 0.75))),
+      knob: Box::new(RectFrame::color((1., 1., 1.))),
+      knob_padding: 2.,
+      animation_duration: None,
+      on_change: None,
+    }
+  }
+
+  pub fn on_change<S: Signal, T: Fn(bool) -> S + 'static>(self, f: T) -> Self {
+    Self {
+      on_change: Some(SignalTriggerArg::new(f)),
+      ..self
+    }
+  }
+
+  pub fn with_track_unchecked(mut self, frame: impl Frame + 'static) -> Self {
+    self.track_unchecked = Box::new(frame);
+    self
+  }
+
+  pub fn with_track_checked(mut self, frame: impl Frame + 'static) -> Self {
+    self.track_checked = Box::new(frame);
+    self
+  }
+
+  pub fn with_knob(mut self, frame: impl Frame + 'static) -> Self {
+    self.knob = Box::new(frame);
+    self
+  }
+}
+
+impl UiElement for Toggle {
+  fn name(&self) -> &'static str {
+    "toggle"
+  }
+
+  fn size(&self) -> Option<Size2d> {
+    Some(self.size)
+  }
+
+  fn measure(&self, ctx: MeasureContext) -> Response {
+    Response {
+      size: compute_size(ctx.layout, self.size, Self::DEFAULT_SIZE.into()),
+      ..Default::default()
+    }
+  }
+
+  fn process(&self, ctx: ProcessContext) {
+    let rect = ctx.measure.rect(ctx.layout.position);
+
+    //Draw the track
+    if self.checked {
+      self.track_checked.draw(ctx.paint_target, rect);
+    } else {
+      self.track_unchecked.draw(ctx.paint_target, rect);
+    }
+
+    //Animate the knob position
+    let target = if self.checked { 1. } else { 0. };
+    let state = ctx.state.acquire_mut_or_insert(self.id, ToggleState { progress: target });
+    state.progress = match self.animation_duration {
+      Some(duration) if duration > 0. => {
+        let step = (ctx.time.delta / duration).max(0.);
+        state.progress + (target - state.progress).clamp(-step, step)
+      },
+      _ => target,
+    };
+    let progress = state.progress;
+
+    //Draw the knob
+    //The knob is a square that fits the height of the track, placed on the left side if off, and on the right side if on
+    let knob_size = (rect.size.y - 2. * self.knob_padding).min(rect.size.x - 2. * self.knob_padding).max(0.);
+    if knob_size > 0. {
+      let travel = rect.size.x - 2. * self.knob_padding - knob_size;
+      self.knob.draw(ctx.paint_target, Rect {
+        position: rect.position + vec2(
+          self.knob_padding + travel * progress,
+          (rect.size.y - knob_size) / 2.,
+        ),
+        size: vec2(knob_size, knob_size),
+      });
+    }
+
+    if ctx.input.check_click(rect).is_some() {
+      if let Some(signal) = &self.on_change {
+        signal.fire(ctx.signal, !self.checked);
+      }
+    }
+  }
+}
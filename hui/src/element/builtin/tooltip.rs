@@ -0,0 +1,147 @@
+//! wrapper that shows a tooltip after hovering over an element for some time
+
+use core::hash::Hash;
+use alloc::boxed::Box;
+use glam::{vec2, Vec2};
+use hui_painter::paint::command::PaintList;
+use crate::{
+  element::{MeasureContext, ProcessContext, UiElement},
+  layout::{Direction, LayoutInfo, Size2d},
+  measure::Response,
+  state::{state_id, State, StateId},
+};
+
+/// State of a [`Tooltip`] wrapper
+#[derive(Clone, Copy, Debug, Default)]
+pub struct TooltipState {
+  /// Time at which the mouse pointer started hovering over the element (if currently hovered)
+  pub hover_start: Option<f32>,
+}
+
+impl State for TooltipState {}
+
+/// Wrapper that shows a tooltip after hovering over an element for some time
+///
+/// The tooltip is drawn near the mouse pointer, on top of the rest of the layer,
+/// and is kept within the rect of the UI root\
+/// Requires the UI clock to be updated (see [`UiInstance::advance_time`](crate::UiInstance::advance_time))
+pub struct Tooltip {
+  /// Id used to store the state of the wrapper
+  pub id: StateId,
+
+  /// The wrapped element
+  pub element: Box<dyn UiElement>,
+
+  /// Element displayed as the tooltip
+  pub tooltip: Box<dyn UiElement>,
+
+  /// Time the element has to be hovered for before the tooltip is shown, in seconds
+  pub delay: f32,
+
+  /// Offset of the tooltip from the mouse pointer
+  pub offset: Vec2,
+}
+
+impl Tooltip {
+  pub const DEFAULT_DELAY: f32 = 0.5;
+
+  pub fn new(id: impl Hash, element: Box<dyn UiElement>, tooltip: Box<dyn UiElement>) -> Self {
+    Self {
+      id: state_id(id),
+      element,
+      tooltip,
+      delay: Self::DEFAULT_DELAY,
+      offset: vec2(12., 16.),
+    }
+  }
+
+  pub fn with_delay(self, delay: f32) -> Self {
+    Self { delay, ..self }
+  }
+
+  pub fn with_offset(self, offset: impl Into<Vec2>) -> Self {
+    Self { offset: offset.into(), ..self }
+  }
+}
+
+impl UiElement for Tooltip {
+  fn name(&self) -> &'static str {
+    "tooltip"
+  }
+
+  fn size(&self) -> Option<Size2d> {
+    self.element.size()
+  }
+
+  fn measure(&self, ctx: MeasureContext) -> Response {
+    self.element.measure(ctx)
+  }
+
+  fn process(&self, ctx: ProcessContext) {
+    let rect = ctx.measure.rect(ctx.layout.position);
+
+    //Track the hover start time
+    let state = ctx.state.acquire_mut_or_default::<TooltipState>(self.id);
+    let visible = if ctx.input.check_hover(rect) {
+      let hover_start = *state.hover_start.get_or_insert(ctx.time.elapsed);
+      ctx.time.elapsed - hover_start >= self.delay
+    } else {
+      state.hover_start = None;
+      false
+    };
+
+    if visible {
+      let root = ctx.root_rect;
+      let layout = LayoutInfo {
+        position: Vec2::ZERO,
+        max_size: root.size,
+        direction: Direction::Vertical,
+        remaining_space: None,
+      };
+      let measure = self.tooltip.measure(MeasureContext {
+        painter: ctx.painter,
+        layout: &layout,
+        state: ctx.state,
+        current_font: ctx.current_font,
+      });
+
+      //Place the tooltip near the mouse pointer, keeping it within the root rect
+      let position = (ctx.input.mouse_position() + self.offset)
+        .min(root.position + root.size - measure.size)
+        .max(root.position);
+
+      //Draw the tooltip into the overlay
+      //Any overlay commands produced by the tooltip itself are drawn on top of it
+      let mut tooltip_overlay = PaintList::new_empty();
+      self.tooltip.process(ProcessContext {
+        painter: ctx.painter,
+        measure: &measure,
+        layout: &LayoutInfo { position, ..layout },
+        paint_target: ctx.paint_overlay,
+        paint_overlay: &mut tooltip_overlay,
+        state: ctx.state,
+        current_font: ctx.current_font,
+        input: ctx.input,
+        signal: ctx.signal,
+        time: ctx.time,
+        root_rect: ctx.root_rect,
+        overlay_occluders: ctx.overlay_occluders,
+      });
+      ctx.paint_overlay.commands.append(&mut tooltip_overlay.commands);
+    }
+
+    self.element.process(ctx);
+  }
+}
+
+/// Extension trait for [`UiElement`] that adds the [`ElementTooltipExt::with_tooltip`] method
+pub trait ElementTooltipExt: UiElement {
+  /// Wrap the element in a [`Tooltip`] that shows the given element after hovering over it for some time
+  fn with_tooltip(self, id: impl Hash, tooltip: impl UiElement + 'static) -> Tooltip;
+}
+
+impl<T: UiElement + 'static> ElementTooltipExt for T {
+  fn with_tooltip(self, id: impl Hash, tooltip: impl UiElement + 'static) -> Tooltip {
+    Tooltip::new(id, Box::new(self), Box::new(tooltip))
+  }
+}
@@ -61,9 +61,13 @@ impl UiElement for Transformer {
       state: ctx.state,
       layout: ctx.layout,
       paint_target: &mut sub_list,
+      paint_overlay: ctx.paint_overlay,
       current_font: ctx.current_font,
       input: ctx.input,
       signal: ctx.signal,
+      time: ctx.time,
+      root_rect: ctx.root_rect,
+      overlay_occluders: ctx.overlay_occluders,
     });
 
     ctx.paint_target.add(PaintTransform {
@@ -0,0 +1,357 @@
+//! a tree view element that displays a hierarchy of expandable nodes
+
+use core::hash::Hash;
+use alloc::{borrow::Cow, boxed::Box, vec::Vec};
+use derive_setters::Setters;
+use glam::{vec2, Affine2, Vec4};
+use hui_painter::{
+  paint::command::{text::PaintText, PaintCommand, PaintTransform},
+  text::FontHandle,
+  PainterInstance,
+};
+use crate::{
+  element::{MeasureContext, ProcessContext, UiElement},
+  frame::{Frame, RectFrame},
+  layout::{compute_size, Size, Size2d},
+  measure::Response,
+  rect::{Rect, Sides},
+  signal::{trigger::SignalTriggerArg, Signal},
+  state::{state_id, State, StateId, StateRepo},
+};
+
+/// Key of the [`TreeNodeState`] within the id scope of the node
+const NODE_STATE_KEY: &str = "tree_node";
+
+/// State of a single [`TreeNode`]
+///
+/// Stored in the [`StateRepo`](crate::state::StateRepo) within the scope of the node
+/// (ids of the tree view and all parent nodes are pushed to the id stack)
+#[derive(Clone, Copy, Debug, Default)]
+pub struct TreeNodeState {
+  /// Whether the children of the node are visible
+  pub expanded: bool,
+}
+
+impl State for TreeNodeState {}
+
+/// A single node of a [`TreeView`]
+pub struct TreeNode {
+  /// Id of the node, must be unique among its siblings
+  pub id: StateId,
+
+  /// Label of the node
+  pub label: Cow<'static, str>,
+
+  /// Whether the node should be expanded by default
+  pub expanded: bool,
+
+  /// Children nodes
+  pub children: Vec<TreeNode>,
+}
+
+impl TreeNode {
+  pub fn new(id: impl Hash, label: impl Into<Cow<'static, str>>) -> Self {
+    Self {
+      id: state_id(id),
+      label: label.into(),
+      expanded: false,
+      children: Vec::new(),
+    }
+  }
+
+  /// Set whether the node should be expanded by default
+  pub fn with_expanded(self, expanded: bool) -> Self {
+    Self { expanded, ..self }
+  }
+
+  /// Add children nodes
+  pub fn with_children(mut self, children: impl IntoIterator<Item = TreeNode>) -> Self {
+    self.children.extend(children);
+    self
+  }
+
+  /// Add a single child node
+  pub fn with_child(mut self, child: TreeNode) -> Self {
+    self.children.push(child);
+    self
+  }
+}
+
+/// A tree view element that displays a hierarchy of expandable nodes
+///
+/// Each node is displayed as a single row, indented based on its depth, with a disclosure arrow if it has children\
+/// Clicking the arrow expands or collapses the node, clicking anywhere else on the row selects it
+///
+/// Node ids passed to the signals are the [`StateId`]s of the nodes (see [`state_id`])
+#[derive(Setters)]
+#[setters(no_std, prefix = "with_")]
+pub struct TreeView {
+  /// Id used to store the state of the element
+  #[setters(skip)]
+  pub id: StateId,
+
+  /// Root nodes of the tree
+  #[setters(skip)]
+  pub nodes: Vec<TreeNode>,
+
+  /// Id of the currently selected node
+  #[setters(skip)]
+  pub selected: Option<StateId>,
+
+  /// Size of the element
+  #[setters(into)]
+  pub size: Size2d,
+
+  /// Indentation of each level of the hierarchy, in pixels
+  pub indent: f32,
+
+  /// Padding inside each of the rows
+  #[setters(into)]
+  pub row_padding: Sides<f32>,
+
+  /// Size of the disclosure arrow, in pixels
+  pub arrow_size: f32,
+
+  /// Gap between the disclosure arrow and the label
+  pub arrow_gap: f32,
+
+  /// Color of the labels
+  #[setters(into)]
+  pub color: Vec4,
+
+  /// Font to use for rendering the labels\
+  /// If set to `None` either currently selected font or the default font will be used
+  #[setters(into)]
+  pub font: Option<FontHandle>,
+
+  /// Size of the labels, in points (these are not pixels)
+  pub text_size: f32,
+
+  /// Disclosure arrow frame used for collapsed nodes
+  #[setters(skip)]
+  pub arrow_collapsed: Box<dyn Frame>,
+
+  /// Disclosure arrow frame used for expanded nodes
+  #[setters(skip)]
+  pub arrow_expanded: Box<dyn Frame>,
+
+  /// Frame drawn behind the row under the mouse pointer
+  #[setters(skip)]
+  pub row_hovered: Box<dyn Frame>,
+
+  /// Frame drawn behind the selected row
+  #[setters(skip)]
+  pub row_selected: Box<dyn Frame>,
+
+  /// Signal that will be fired with the id of the node when it gets selected
+  #[setters(skip)]
+  pub on_select: Option<SignalTriggerArg<StateId>>,
+
+  /// Signal that will be fired with the id of the node and its new expanded state when it gets expanded or collapsed
+  #[setters(skip)]
+  pub on_expand: Option<SignalTriggerArg<(StateId, bool)>>,
+}
+
+impl TreeView {
+  pub fn new(id: impl Hash) -> Self {
+    Self {
+      id: state_id(id),
+      nodes: Vec::new(),
+      selected: None,
+      size: (Size::Auto, Size::Auto).into(),
+      indent: 16.,
+      row_padding: Sides::horizontal_vertical(5., 2.),
+      arrow_size: 8.,
+      arrow_gap: 5.,
+      color: Vec4::new(1., 1., 1., 1.),
+      font: None,
+      text_size: 16.,
+      arrow_collapsed: Box::new(RectFrame::color((0.5, 0.5, 0.5))),
+      arrow_expanded: Box::new(RectFrame::color((0.8, 0.8, 0.8))),
+      row_hovered: Box::new(RectFrame::color((1., 1., 1., 0.1))),
+      row_selected: Box::new(RectFrame::color((0.0, 0.0, 0.75))),
+      on_select: None,
+      on_expand: None,
+    }
+  }
+
+  /// Add root nodes
+  pub fn with_nodes(mut self, nodes: impl IntoIterator<Item = TreeNode>) -> Self {
+    self.nodes.extend(nodes);
+    self
+  }
+
+  /// Set the currently selected node
+  pub fn with_selected(self, id: impl Hash) -> Self {
+    Self {
+      selected: Some(state_id(id)),
+      ..self
+    }
+  }
+
+  pub fn on_select<S: Signal, T: Fn(StateId) -> S + 'static>(self, f: T) -> Self {
+    Self {
+      on_select: Some(SignalTriggerArg::new(f)),
+      ..self
+    }
+  }
+
+  pub fn on_expand<S: Signal, T: Fn((StateId, bool)) -> S + 'static>(self, f: T) -> Self {
+    Self {
+      on_expand: Some(SignalTriggerArg::new(f)),
+      ..self
+    }
+  }
+
+  pub fn with_arrow_collapsed(mut self, frame: impl Frame + 'static) -> Self {
+    self.arrow_collapsed = Box::new(frame);
+    self
+  }
+
+  pub fn with_arrow_expanded(mut self, frame: impl Frame + 'static) -> Self {
+    self.arrow_expanded = Box::new(frame);
+    self
+  }
+
+  pub fn with_row_hovered(mut self, frame: impl Frame + 'static) -> Self {
+    self.row_hovered = Box::new(frame);
+    self
+  }
+
+  pub fn with_row_selected(mut self, frame: impl Frame + 'static) -> Self {
+    self.row_selected = Box::new(frame);
+    self
+  }
+
+  fn paint_text(&self, text: Cow<'static, str>, current_font: FontHandle) -> PaintText {
+    PaintText::new(text, self.font.unwrap_or(current_font), self.text_size, self.color)
+  }
+
+  fn row_height(&self, painter: &PainterInstance, current_font: FontHandle) -> f32 {
+    self.paint_text("".into(), current_font).line_height(painter) + self.row_padding.sum_vertical()
+  }
+
+  /// Offset of the label from the start of the row (excluding the indentation)
+  fn label_offset(&self) -> f32 {
+    self.row_padding.left + self.arrow_size + self.arrow_gap
+  }
+
+  /// Count the visible rows, and compute the width of the widest one
+  fn measure_nodes(
+    &self,
+    nodes: &[TreeNode],
+    path: &mut Vec<StateId>,
+    state: &StateRepo,
+    label_width: &dyn Fn(&TreeNode) -> f32,
+    rows: &mut usize,
+    width: &mut f32,
+  ) {
+    let depth = path.len() - 1;
+    for node in nodes {
+      *rows += 1;
+      *width = width.max(
+        depth as f32 * self.indent + self.label_offset() + label_width(node) + self.row_padding.right
+      );
+      path.push(node.id);
+      let expanded = state.peek_nested::<TreeNodeState>(path, NODE_STATE_KEY)
+        .map_or(node.expanded, |node_state| node_state.expanded);
+      if expanded {
+        self.measure_nodes(&node.children, path, state, label_width, rows, width);
+      }
+      path.pop();
+    }
+  }
+
+  /// Draw the visible rows and handle the input
+  ///
+  /// Must be called with the id of the parent node (or the tree view itself) pushed to the id stack
+  fn process_nodes(&self, nodes: &[TreeNode], depth: usize, row_height: f32, ctx: &mut ProcessContext, y: &mut f32) {
+    for node in nodes {
+      let row_rect = Rect::new(
+        ctx.layout.position + vec2(0., *y),
+        vec2(ctx.measure.size.x, row_height),
+      );
+      let indent = depth as f32 * self.indent;
+      let arrow_rect = Rect::new(
+        row_rect.position + vec2(indent + self.row_padding.left, (row_height - self.arrow_size) / 2.),
+        vec2(self.arrow_size, self.arrow_size),
+      );
+      *y += row_height;
+
+      ctx.state.push(node.id);
+      let node_state = ctx.state.acquire_mut_or_insert(NODE_STATE_KEY, TreeNodeState {
+        expanded: node.expanded,
+      });
+
+      //Handle the input
+      let has_children = !node.children.is_empty();
+      if has_children && ctx.input.check_click(arrow_rect).is_some() {
+        node_state.expanded = !node_state.expanded;
+        if let Some(signal) = &self.on_expand {
+          signal.fire(ctx.signal, (node.id, node_state.expanded));
+        }
+      } else if self.selected != Some(node.id) && ctx.input.check_click(row_rect).is_some() {
+        if let Some(signal) = &self.on_select {
+          signal.fire(ctx.signal, node.id);
+        }
+      }
+      let expanded = node_state.expanded;
+
+      //Draw the row
+      if self.selected == Some(node.id) {
+        self.row_selected.draw(ctx.paint_target, row_rect);
+      }
+      if ctx.input.check_hover(row_rect) {
+        self.row_hovered.draw(ctx.paint_target, row_rect);
+      }
+      if has_children {
+        let arrow = if expanded { &self.arrow_expanded } else { &self.arrow_collapsed };
+        arrow.draw(ctx.paint_target, arrow_rect);
+      }
+      if self.color.w > 0. {
+        ctx.paint_target.add(PaintTransform {
+          transform: Affine2::from_translation(row_rect.position + vec2(indent + self.label_offset(), self.row_padding.top)),
+          child: self.paint_text(node.label.clone(), ctx.current_font),
+        });
+      }
+
+      if expanded {
+        self.process_nodes(&node.children, depth + 1, row_height, ctx, y);
+      }
+      ctx.state.pop();
+    }
+  }
+}
+
+impl UiElement for TreeView {
+  fn name(&self) -> &'static str {
+    "tree_view"
+  }
+
+  fn size(&self) -> Option<Size2d> {
+    Some(self.size)
+  }
+
+  fn measure(&self, ctx: MeasureContext) -> Response {
+    let label_width = |node: &TreeNode| {
+      self.paint_text(node.label.clone(), ctx.current_font).bounds(ctx.painter).size.x
+    };
+    let mut rows = 0;
+    let mut width = 0.;
+    self.measure_nodes(&self.nodes, &mut vec![self.id], ctx.state, &label_width, &mut rows, &mut width);
+    Response {
+      size: compute_size(ctx.layout, self.size, vec2(
+        width,
+        rows as f32 * self.row_height(ctx.painter, ctx.current_font),
+      )),
+      ..Default::default()
+    }
+  }
+
+  fn process(&self, mut ctx: ProcessContext) {
+    let row_height = self.row_height(ctx.painter, ctx.current_font);
+    ctx.state.push(self.id);
+    self.process_nodes(&self.nodes, 0, row_height, &mut ctx, &mut 0.);
+    ctx.state.pop();
+  }
+}
@@ -0,0 +1,361 @@
+//! a scrollable list element that only builds the rows currently in view
+
+use core::hash::Hash;
+use alloc::{boxed::Box, collections::BTreeMap, vec::Vec};
+use derive_setters::Setters;
+use glam::vec2;
+use hui_painter::paint::command::{PaintClip, PaintList};
+use crate::{
+  element::{scrollbar::Scrollbar, ElementList, MeasureContext, ProcessContext, UiElement},
+  frame::{Frame, RectFrame},
+  layout::{compute_size, Direction, LayoutInfo, Size, Size2d},
+  measure::Response,
+  rect::Rect,
+  state::{state_id, State, StateId},
+};
+
+/// State of a [`VirtualList`] element
+///
+/// The scroll position is stored relative to the first visible row,
+/// so that rows of unknown height above it don't need to be measured
+#[derive(Clone, Copy, Debug, Default)]
+pub struct VirtualListState {
+  /// Index of the first visible row
+  pub first_row: usize,
+
+  /// Offset from the top of the first visible row to the top of the list, in pixels
+  pub row_offset: f32,
+
+  /// Scroll position at the time the scrollbar started being dragged
+  pub drag_start: Option<f32>,
+}
+
+impl State for VirtualListState {}
+
+/// Height of the rows of a [`VirtualList`]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum RowHeight {
+  /// All rows have exactly the same height, in pixels
+  Fixed(f32),
+
+  /// Rows are measured as they come into view, and the given height, in pixels, is only used
+  /// to estimate the total size of the list (which affects the scrollbar)
+  Estimated(f32),
+}
+
+impl RowHeight {
+  /// Get the (fixed or estimated) height of a single row
+  pub fn estimate(&self) -> f32 {
+    match self {
+      Self::Fixed(height) | Self::Estimated(height) => *height,
+    }
+  }
+}
+
+/// Callback building the elements of the row with the given index
+pub type VirtualRowFn = dyn Fn(usize, &mut ElementList);
+
+/// A single row built during the current frame
+struct VirtualRow {
+  elements: ElementList,
+  measures: Vec<Response>,
+  height: f32,
+}
+
+/// A vertically scrolling list that only builds and processes the rows currently in view
+///
+/// Rows are built on demand by the `rows` callback, which is called with the index of each visible row\
+/// All elements added by the callback are stacked vertically and treated as a single row
+///
+/// Can be scrolled using the mouse wheel or by dragging the scrollbar\
+/// The scroll position is stored in the [`StateRepo`](crate::state::StateRepo) under the given id
+#[derive(Setters)]
+#[setters(no_std, prefix = "with_")]
+pub struct VirtualList {
+  /// Id used to store the state of the element
+  #[setters(skip)]
+  pub id: StateId,
+
+  /// Total number of rows
+  pub row_count: usize,
+
+  /// Height of the rows
+  pub row_height: RowHeight,
+
+  /// Callback building the elements of each row
+  #[setters(skip)]
+  pub rows: Option<Box<VirtualRowFn>>,
+
+  /// Size of the element (visible area)
+  #[setters(into)]
+  pub size: Size2d,
+
+  /// Width of the scrollbar, in pixels
+  ///
+  /// Set to `0.0` to hide the scrollbar
+  pub scrollbar_width: f32,
+
+  /// Minimum length of the scrollbar handle, in pixels
+  pub scrollbar_min_length: f32,
+
+  /// Scrollbar track frame
+  #[setters(skip)]
+  pub scrollbar_track: Box<dyn Frame>,
+
+  /// Scrollbar handle frame
+  #[setters(skip)]
+  pub scrollbar: Box<dyn Frame>,
+}
+
+impl VirtualList {
+  pub fn new(id: impl Hash, row_count: usize, row_height: RowHeight) -> Self {
+    Self {
+      id: state_id(id),
+      row_count,
+      row_height,
+      rows: None,
+      size: (Size::Relative(1.), Size::Relative(1.)).into(),
+      scrollbar_width: 8.,
+      scrollbar_min_length: 16.,
+      scrollbar_track: Box::new(RectFrame::color((0.1, 0.1, 0.1, 0.5))),
+      scrollbar: Box::new(RectFrame::color((0.5, 0.5, 0.5))),
+    }
+  }
+
+  pub fn with_rows(mut self, rows: impl Fn(usize, &mut ElementList) + 'static) -> Self {
+    self.rows = Some(Box::new(rows));
+    self
+  }
+
+  pub fn with_scrollbar_track(mut self, frame: impl Frame + 'static) -> Self {
+    self.scrollbar_track = Box::new(frame);
+    self
+  }
+
+  pub fn with_scrollbar(mut self, frame: impl Frame + 'static) -> Self {
+    self.scrollbar = Box::new(frame);
+    self
+  }
+
+  /// Build and measure the row with the given index, unless it was already built this frame
+  fn row<'a>(&self, rows: &'a mut BTreeMap<usize, VirtualRow>, index: usize, ctx: &MeasureContext) -> &'a VirtualRow {
+    rows.entry(index).or_insert_with(|| {
+      let mut elements = ElementList(Vec::new());
+      if let Some(build) = &self.rows {
+        build(index, &mut elements);
+      }
+      let measures: Vec<Response> = elements.0.iter().map(|element| element.measure(MeasureContext {
+        painter: ctx.painter,
+        current_font: ctx.current_font,
+        layout: ctx.layout,
+        state: ctx.state,
+      })).collect();
+      let height = match self.row_height {
+        RowHeight::Fixed(height) => height,
+        RowHeight::Estimated(_) => measures.iter().map(|measure| measure.size.y).sum(),
+      };
+      VirtualRow { elements, measures, height }
+    })
+  }
+
+  /// Normalize the scroll position, so that the offset points inside of the first visible row
+  fn normalize(&self, state: &mut VirtualListState, rows: &mut BTreeMap<usize, VirtualRow>, ctx: &MeasureContext) {
+    loop {
+      if state.row_offset < 0. {
+        if state.first_row == 0 {
+          state.row_offset = 0.;
+          break
+        }
+        state.first_row -= 1;
+        state.row_offset += self.row(rows, state.first_row, ctx).height;
+      } else {
+        let height = self.row(rows, state.first_row, ctx).height;
+        if state.first_row + 1 >= self.row_count || state.row_offset < height {
+          break
+        }
+        state.row_offset -= height;
+        state.first_row += 1;
+      }
+    }
+  }
+  /// Lay out the rows within a viewport of the given height, starting from the current scroll position
+  ///
+  /// Returns the index and vertical offset of each visible row, and whether the rows overflow the viewport
+  fn layout_rows(
+    &self,
+    state: &mut VirtualListState,
+    rows: &mut BTreeMap<usize, VirtualRow>,
+    ctx: &MeasureContext,
+    height: f32,
+  ) -> (Vec<(usize, f32)>, bool) {
+    //Make sure there's no empty space left after the last row
+    if self.row_count > 0 {
+      self.normalize(state, rows, ctx);
+      let mut bottom = -state.row_offset;
+      let mut index = state.first_row;
+      while bottom < height && index < self.row_count {
+        bottom += self.row(rows, index, ctx).height;
+        index += 1;
+      }
+      if bottom < height {
+        state.row_offset -= height - bottom;
+        self.normalize(state, rows, ctx);
+      }
+    }
+
+    //Build the rest of the visible rows
+    let mut visible = Vec::new();
+    let mut y = -state.row_offset;
+    let mut index = state.first_row;
+    while y < height && index < self.row_count {
+      let row_height = self.row(rows, index, ctx).height;
+      visible.push((index, y));
+      y += row_height;
+      index += 1;
+    }
+
+    //Rows overflow if any of them are outside of the viewport, even partially
+    let overflows = state.first_row > 0 || state.row_offset > 0. || y > height || index < self.row_count;
+    (visible, overflows)
+  }
+}
+
+impl UiElement for VirtualList {
+  fn name(&self) -> &'static str {
+    "virtual_list"
+  }
+
+  fn size(&self) -> Option<Size2d> {
+    Some(self.size)
+  }
+
+  fn measure(&self, ctx: MeasureContext) -> Response {
+    Response {
+      size: compute_size(ctx.layout, self.size, ctx.layout.max_size),
+      ..Default::default()
+    }
+  }
+
+  fn process(&self, ctx: ProcessContext) {
+    let rect = ctx.measure.rect(ctx.layout.position);
+    let estimate = self.row_height.estimate();
+
+    //Scrollbar geometry
+    //The total length is only an estimate if the row heights are not fixed
+    let total_length = self.row_count as f32 * estimate;
+    let track_rect = Rect::new(
+      rect.position + vec2(rect.size.x - self.scrollbar_width, 0.),
+      vec2(self.scrollbar_width, rect.size.y),
+    );
+    let scrollbar = Scrollbar::new(track_rect, Direction::Vertical, total_length, self.scrollbar_min_length);
+
+    let mut initial_state = *ctx.state.acquire_or_default::<VirtualListState>(self.id);
+    if self.row_count == 0 {
+      initial_state.first_row = 0;
+      initial_state.row_offset = 0.;
+    } else {
+      initial_state.first_row = initial_state.first_row.min(self.row_count - 1);
+    }
+
+    //Whether the rows overflow the visible area is guessed from the estimated total length first,
+    //and if the measured rows disagree, they are laid out again with the space for the scrollbar reserved (or released)
+    let mut show_scrollbar = self.scrollbar_width > 0. && total_length > rect.size.y;
+    let mut retried = false;
+    let (state, content_rect, rows, visible, overflows) = loop {
+      let mut state = initial_state;
+      let content_rect = match show_scrollbar {
+        true => Rect::new(rect.position, rect.size - vec2(self.scrollbar_width, 0.)),
+        false => rect,
+      };
+
+      //Rows are measured as if they were laid out in a vertical container of the same width
+      let row_layout = LayoutInfo {
+        position: content_rect.position,
+        max_size: vec2(content_rect.size.x, match self.row_height {
+          RowHeight::Fixed(height) => height,
+          RowHeight::Estimated(_) => content_rect.size.y,
+        }),
+        direction: Direction::Vertical,
+        remaining_space: None,
+      };
+      let measure_ctx = MeasureContext {
+        painter: ctx.painter,
+        current_font: ctx.current_font,
+        layout: &row_layout,
+        state: ctx.state,
+      };
+      let mut rows = BTreeMap::new();
+
+      //Scrollbar dragging
+      //Scroll position is computed from the estimated row height, with the offset in the first row scaled accordingly
+      if show_scrollbar && self.row_count > 0 {
+        let first_row_height = self.row(&mut rows, state.first_row, &measure_ctx).height;
+        let position = (state.first_row as f32 + state.row_offset / first_row_height.max(1.)) * estimate;
+        if let Some(position) = scrollbar.process(&ctx.input, position, &mut state.drag_start) {
+          let position = position / estimate;
+          state.first_row = (position as usize).min(self.row_count - 1);
+          state.row_offset = position.fract() * self.row(&mut rows, state.first_row, &measure_ctx).height;
+        }
+      } else {
+        state.drag_start = None;
+      }
+
+      let (visible, overflows) = self.layout_rows(&mut state, &mut rows, &measure_ctx, content_rect.size.y);
+      if retried || show_scrollbar == (overflows && self.scrollbar_width > 0.) {
+        break (state, content_rect, rows, visible, overflows)
+      }
+      show_scrollbar = !show_scrollbar;
+      retried = true;
+    };
+    let position = match self.row_count {
+      0 => 0.,
+      _ => (state.first_row as f32 + state.row_offset / rows[&state.first_row].height.max(1.)) * estimate,
+    };
+    *ctx.state.acquire_mut_or_default::<VirtualListState>(self.id) = state;
+
+    //Process the visible rows, clipping both the paint commands and the input to the visible area
+    let mut sub_list = PaintList::new_empty();
+    for (index, y) in visible {
+      let row = &rows[&index];
+      let mut element_y = y;
+      for (element, measure) in row.elements.0.iter().zip(&row.measures) {
+        element.process(ProcessContext {
+          painter: ctx.painter,
+          measure,
+          layout: &LayoutInfo {
+            position: content_rect.position + vec2(0., element_y.round()),
+            max_size: vec2(content_rect.size.x, row.height),
+            direction: Direction::Vertical,
+            remaining_space: None,
+          },
+          paint_target: &mut sub_list,
+          paint_overlay: ctx.paint_overlay,
+          state: ctx.state,
+          current_font: ctx.current_font,
+          input: ctx.input.with_clip(content_rect),
+          signal: ctx.signal,
+          time: ctx.time,
+          root_rect: ctx.root_rect,
+          overlay_occluders: ctx.overlay_occluders,
+        });
+        element_y += measure.size.y;
+      }
+    }
+    ctx.paint_target.add(PaintClip {
+      rect: content_rect,
+      child: sub_list,
+    });
+
+    //Mouse wheel
+    //(handled after the rows, so that the nested scrollable elements can consume it first)
+    if overflows && ctx.input.check_hover(rect) {
+      let delta = ctx.input.consume_wheel_delta().y;
+      ctx.state.acquire_mut_or_default::<VirtualListState>(self.id).row_offset -= delta;
+    }
+
+    //Draw the scrollbar
+    if show_scrollbar {
+      scrollbar.draw(ctx.paint_target, &*self.scrollbar_track, &*self.scrollbar, position);
+    }
+  }
+}
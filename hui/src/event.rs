@@ -7,6 +7,9 @@ use crate::input::{MouseButton, ButtonState, KeyboardKey};
 #[derive(Clone, Copy, Debug)]
 pub enum UiEvent {
   MouseMove(Vec2),
+  /// Mouse wheel (or touchpad) scroll, in pixels\
+  /// Positive values mean scrolling up/left (content moving down/right)
+  MouseWheel(Vec2),
   MouseButton {
     button: MouseButton,
     state: ButtonState,
@@ -1,6 +1,6 @@
 //! keyboard, mouse, and touch input handling
 
-use core::hash::{Hash, Hasher};
+use core::{cell::Cell, hash::{Hash, Hasher}};
 use alloc::vec::Vec;
 use glam::Vec2;
 use hashbrown::HashMap;
@@ -155,6 +155,12 @@ pub struct MouseState {
 
   /// mouse buttons that were released *in the current frame*
   pub released_buttons: HashMap<MouseButton, MouseButtonMeta, BuildNoHashHasher<u16>>,
+
+  /// mouse buttons that were pressed down *in the current frame*
+  pub pressed_buttons: HashMap<MouseButton, MouseButtonMeta, BuildNoHashHasher<u16>>,
+
+  /// Total mouse wheel movement *in the current frame*, in pixels
+  pub wheel_delta: Vec2,
 }
 
 /// Unique identifier of a touch pointer (finger)
@@ -174,6 +180,8 @@ pub(crate) struct UiInputState {
   keyboard_state: Set64<KeyboardKey>,
   /// events that happened in the current frame
   just_happened: Vec<UiEvent>,
+  /// the mouse wheel movement was already consumed by an element in the current frame
+  wheel_consumed: Cell<bool>,
 }
 
 impl UiInputState {
@@ -183,6 +191,7 @@ impl UiInputState {
       mouse_pointer: MouseState::default(),
       keyboard_state: Set64::new(),
       just_happened: Vec::new(),
+      wheel_consumed: Cell::new(false),
     }
   }
 
@@ -192,6 +201,9 @@ impl UiInputState {
   pub fn update_state(&mut self, event_queue: &mut EventQueue) {
     self.mouse_pointer.prev_position = self.mouse_pointer.current_position;
     self.mouse_pointer.released_buttons.clear();
+    self.mouse_pointer.pressed_buttons.clear();
+    self.mouse_pointer.wheel_delta = Vec2::ZERO;
+    self.wheel_consumed.set(false);
     self.just_happened.clear();
     self.just_happened.extend(event_queue.drain());
     for event in &self.just_happened {
@@ -200,14 +212,18 @@ impl UiInputState {
         UiEvent::MouseMove(pos) => {
           self.mouse_pointer.current_position = *pos;
         },
+        UiEvent::MouseWheel(delta) => {
+          self.mouse_pointer.wheel_delta += *delta;
+        },
         UiEvent::MouseButton { button, state } => {
           match state {
             //wtf should we do with buttons that are pressed and released in the same frame?
             //i have no fvcking idea
             ButtonState::Pressed => {
-              let button = self.mouse_pointer.buttons.entry(*button)
+              let meta = self.mouse_pointer.buttons.entry(*button)
                 .or_insert(MouseButtonMeta::default());
-              button.start_position = self.mouse_pointer.current_position;
+              meta.start_position = self.mouse_pointer.current_position;
+              self.mouse_pointer.pressed_buttons.insert(*button, *meta);
             },
             ButtonState::Released => {
               //log::trace!("Button {:?} was released", button);
@@ -230,14 +246,22 @@ impl UiInputState {
             ButtonState::Released => self.keyboard_state.remove(key),
           };
         },
-        //TODO touch, text input
-        _ => (),
+        //Text input does not affect the persistent state,
+        //it's only accessible through `just_happened` during the current frame
+        UiEvent::TextInput(_) => (),
+        //TODO touch
       }
     }
   }
 
   pub fn ctx(&self) -> InputCtx {
-    InputCtx(self)
+    InputCtx {
+      state: self,
+      clip: None,
+      occluders: &[],
+      blocked: false,
+      keyboard_blocked: false,
+    }
   }
 }
 
@@ -254,28 +278,130 @@ pub struct ActiveCheckResponse {
   pub last_position_in_rect: Vec2,
 }
 
+/// Read-only access to the input state
+///
+/// Optionally restricted to a clipping rectangle (see [`InputCtx::with_clip`]),
+/// and blocked by the layers above the current one
 #[derive(Clone, Copy)]
-pub struct InputCtx<'a>(&'a UiInputState);
+pub struct InputCtx<'a> {
+  state: &'a UiInputState,
+
+  /// Pointer input outside of this rect is ignored
+  clip: Option<Rect>,
+
+  /// Pointer input inside of these rects is ignored (covered by the layers above)
+  occluders: &'a [Rect],
+
+  /// All input is ignored (blocked by a modal layer above)
+  blocked: bool,
+
+  /// Keyboard input is ignored (blocked by a popup layer above)
+  keyboard_blocked: bool,
+}
+
+impl<'a> InputCtx<'a> {
+  /// Block the input covered by the layers above the current one
+  ///
+  /// - Pointer input inside of any of the `occluders` rects is ignored
+  /// - If `blocked` is set, all input is ignored
+  /// - If `keyboard_blocked` is set, keyboard and text input is ignored
+  pub(crate) fn with_occluders(self, occluders: &'a [Rect], blocked: bool, keyboard_blocked: bool) -> Self {
+    Self {
+      occluders,
+      blocked: self.blocked || blocked,
+      keyboard_blocked: self.keyboard_blocked || keyboard_blocked,
+      ..self
+    }
+  }
+
+  /// Restrict all pointer checks (hover, click, etc.) to the given rectangle\
+  /// (intersected with the current clipping rectangle, if any)
+  ///
+  /// Elements that clip their children (e.g. scroll views) should pass the clipped input context down to them,
+  /// so that the parts that are not visible do not react to the pointer
+  pub fn with_clip(self, rect: Rect) -> Self {
+    let clip = match self.clip {
+      //If the rects do not intersect, nothing can be interacted with
+      Some(current) => current.intersection(rect).unwrap_or(Rect::new(Vec2::splat(f32::NEG_INFINITY), Vec2::ZERO)),
+      None => rect,
+    };
+    Self {
+      clip: Some(clip),
+      ..self
+    }
+  }
+
+  /// Get the input context for the contents drawn into the overlay (e.g. popups)
+  ///
+  /// Overlay is drawn on top of the rest of the layer, so the clipping rectangle and the occluders are ignored\
+  /// (input blocked by the layers above stays blocked)
+  pub fn overlay(self) -> Self {
+    Self {
+      clip: None,
+      occluders: &[],
+      ..self
+    }
+  }
+
+  /// Check if the pointer input at the given point should be accepted\
+  /// (not blocked, inside of the clipping rectangle, and not covered by the layers above)
+  fn accepts_pointer(&self, point: Vec2) -> bool {
+    !self.blocked &&
+    self.clip.is_none_or(|clip| clip.contains_point(point)) &&
+    !self.occluders.iter().any(|rect| rect.contains_point(point))
+  }
+
+  /// Check if the input is blocked entirely (e.g. by a modal layer above the current one)
+  pub fn is_blocked(&self) -> bool {
+    self.blocked
+  }
+
+  /// Check if the keyboard input is blocked (e.g. by a popup or a modal layer above the current one)
+  pub fn is_keyboard_blocked(&self) -> bool {
+    self.blocked || self.keyboard_blocked
+  }
+
+  /// Get the total mouse wheel movement in the current frame, in pixels
+  ///
+  /// Positive values mean scrolling up/left (content moving down/right)
+  ///
+  /// Returns zero if the wheel movement was already consumed by another element (see [`InputCtx::consume_wheel_delta`])
+  pub fn wheel_delta(&self) -> Vec2 {
+    if self.state.wheel_consumed.get() || !self.accepts_pointer(self.state.mouse_pointer.current_position) {
+      return Vec2::ZERO
+    }
+    self.state.mouse_pointer.wheel_delta
+  }
+
+  /// Same as [`InputCtx::wheel_delta`], but prevents other elements from receiving the wheel movement in the current frame
+  ///
+  /// Scrollable elements should process their children first, so that the innermost one gets to consume the wheel movement
+  pub fn consume_wheel_delta(&self) -> Vec2 {
+    let delta = self.wheel_delta();
+    if delta != Vec2::ZERO {
+      self.state.wheel_consumed.set(true);
+    }
+    delta
+  }
 
-impl InputCtx<'_> {
   /// Get the current position of the mouse pointer
   ///
   /// Do not use this function to check for hover, use [`InputCtx::check_hover`] instead
   pub fn mouse_position(&self) -> Vec2 {
-    self.0.mouse_pointer.current_position
+    self.state.mouse_pointer.current_position
   }
 
   /// Get the current position of the mouse pointer within a rectangle
   ///
   /// Do not use this function to check for hover, use [`InputCtx::check_hover`] instead
   pub fn mouse_position_in_rect(&self, rect: Rect) -> Option<Vec2> {
-    let pos = self.0.mouse_pointer.current_position;
-    rect.contains_point(pos).then_some(pos - rect.position)
+    let pos = self.state.mouse_pointer.current_position;
+    (rect.contains_point(pos) && self.accepts_pointer(pos)).then_some(pos - rect.position)
   }
 
   /// Get the state of a mouse button
   pub fn mouse_button_down(&self, button: MouseButton) -> ButtonState {
-    self.0.mouse_pointer.buttons.contains_key(&button).into()
+    (!self.blocked && self.state.mouse_pointer.buttons.contains_key(&button)).into()
   }
 
   /// Get the start position of a mouse button\
@@ -283,7 +409,21 @@ impl InputCtx<'_> {
   ///
   /// Returns `None` if the button is not currently down
   pub fn mouse_button_start_position(&self, button: MouseButton) -> Option<Vec2> {
-    self.0.mouse_pointer.buttons.get(&button).map(|meta| meta.start_position)
+    self.state.mouse_pointer.buttons.get(&button)
+      .filter(|_| !self.blocked)
+      .map(|meta| meta.start_position)
+  }
+
+  /// Check if a mouse button was pressed down in the current frame
+  pub fn mouse_button_just_pressed(&self, button: MouseButton) -> bool {
+    self.accepts_pointer(self.state.mouse_pointer.current_position) &&
+    self.state.mouse_pointer.pressed_buttons.contains_key(&button)
+  }
+
+  /// Check if a mouse button was released in the current frame
+  pub fn mouse_button_just_released(&self, button: MouseButton) -> bool {
+    self.accepts_pointer(self.state.mouse_pointer.current_position) &&
+    self.state.mouse_pointer.released_buttons.contains_key(&button)
   }
 
   /// Get the relative movement of the mouse pointer since the button was pressed down
@@ -294,11 +434,57 @@ impl InputCtx<'_> {
     Some(self.mouse_position() - start)
   }
 
+  /// Get the state of a keyboard key
+  pub fn key_down(&self, key: KeyboardKey) -> ButtonState {
+    (!self.is_keyboard_blocked() && self.state.keyboard_state.contains(key)).into()
+  }
+
+  /// Check if either of the shift keys is currently held down
+  pub fn shift_down(&self) -> bool {
+    self.key_down(KeyboardKey::LShift).is_pressed() ||
+    self.key_down(KeyboardKey::RShift).is_pressed()
+  }
+
+  /// Check if either of the control keys is currently held down
+  pub fn control_down(&self) -> bool {
+    self.key_down(KeyboardKey::LControl).is_pressed() ||
+    self.key_down(KeyboardKey::RControl).is_pressed()
+  }
+
+  /// Get all events that happened in the current frame (in the order they were received)
+  ///
+  /// Returns no events if the keyboard input is blocked
+  pub fn events(&self) -> &'a [UiEvent] {
+    if self.is_keyboard_blocked() {
+      return &[]
+    }
+    &self.state.just_happened
+  }
+
+  /// Iterate over all keys that were pressed down in the current frame (in order)
+  ///
+  /// If the platform backend reports key repeats as separate key presses, those will be included as well
+  pub fn keys_pressed(&self) -> impl Iterator<Item = KeyboardKey> + 'a {
+    self.events().iter().filter_map(|event| match event {
+      UiEvent::KeyboardButton { key, state: ButtonState::Pressed } => Some(*key),
+      _ => None,
+    })
+  }
+
+  /// Iterate over all characters that were typed in the current frame (in order)
+  pub fn text_input(&self) -> impl Iterator<Item = char> + 'a {
+    self.events().iter().filter_map(|event| match event {
+      UiEvent::TextInput(c) => Some(*c),
+      _ => None,
+    })
+  }
+
   /// Check if a rect can be considered "hovered"
   ///
   /// This can be triggered by multiple input sources, such as mouse, touch, etc.
   pub fn check_hover(&self, rect: Rect) -> bool {
-    rect.contains_point(self.0.mouse_pointer.current_position)
+    let pos = self.state.mouse_pointer.current_position;
+    rect.contains_point(pos) && self.accepts_pointer(pos)
   }
 
   /// Check if a rect can be considered "clicked" in the current frame
@@ -309,17 +495,23 @@ impl InputCtx<'_> {
   /// - The mouse pointer is currently inside the rectangle
   /// - The mouse pointer was inside the rectangle at the time the button was pressed down
   ///
-  /// By default, this function only checks for the primary mouse button\
-  /// This is a limitation of the current API and may change in the future\
-  /// (as the current implementation of this function checks for both mouse and touch input, and the touch input quite obviously only supports one "button")
+  /// This function only checks for the primary mouse button\
+  /// (as the touch input quite obviously only supports one "button"),
+  /// use [`InputCtx::check_click_with_button`] to check for other mouse buttons
   pub fn check_click(&self, rect: Rect) -> Option<ActiveCheckResponse> {
-    let pos = self.0.mouse_pointer.current_position;
-    self.0.mouse_pointer.released_buttons.get(&MouseButton::Primary).filter(|meta| {
-      rect.contains_point(meta.start_position) && rect.contains_point(pos)
+    self.check_click_with_button(rect, MouseButton::Primary)
+  }
+
+  /// Same as [`InputCtx::check_click`], but checks for the specified mouse button
+  pub fn check_click_with_button(&self, rect: Rect, button: MouseButton) -> Option<ActiveCheckResponse> {
+    let pos = self.state.mouse_pointer.current_position;
+    self.state.mouse_pointer.released_buttons.get(&button).filter(|meta| {
+      rect.contains_point(meta.start_position) && rect.contains_point(pos) &&
+      self.accepts_pointer(meta.start_position) && self.accepts_pointer(pos)
     }).map(|mi| ActiveCheckResponse {
       position_in_rect: pos - rect.position,
       start_position_in_rect: mi.start_position - rect.position,
-      last_position_in_rect: self.0.mouse_pointer.prev_position - rect.position,
+      last_position_in_rect: self.state.mouse_pointer.prev_position - rect.position,
     })
   }
 
@@ -327,12 +519,12 @@ impl InputCtx<'_> {
 
   /// Check if a rect is being actively being interacted with (e.g. dragged)
   pub fn check_active(&self, rect: Rect) -> Option<ActiveCheckResponse> {
-    self.0.mouse_pointer.buttons.get(&MouseButton::Primary).filter(|mi| {
-      rect.contains_point(mi.start_position)
+    self.state.mouse_pointer.buttons.get(&MouseButton::Primary).filter(|mi| {
+      rect.contains_point(mi.start_position) && self.accepts_pointer(mi.start_position)
     }).map(|mi| ActiveCheckResponse {
-      position_in_rect: self.0.mouse_pointer.current_position - rect.position,
+      position_in_rect: self.state.mouse_pointer.current_position - rect.position,
       start_position_in_rect: mi.start_position - rect.position,
-      last_position_in_rect: self.0.mouse_pointer.prev_position - rect.position,
+      last_position_in_rect: self.state.mouse_pointer.prev_position - rect.position,
     })
   }
 }
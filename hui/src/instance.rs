@@ -1,3 +1,5 @@
+use core::ops::Range;
+use alloc::vec::Vec;
 use hui_painter::{
   backend::BackendData, paint::command::{PaintCommand, PaintList}, presentation::Presentatation, text::{FontHandle, FontManager}, texture::{SourceTextureFormat, TextureAtlas, TextureHandle}, PainterInstance
 };
@@ -5,13 +7,37 @@ use crate::{
   element::{MeasureContext, ProcessContext, UiElement},
   event::{EventQueue, UiEvent},
   font::FontStack,
-  input::UiInputState,
+  input::{KeyboardKey, MouseButton, UiInputState},
+  layer::{Layer, LayerKind},
   layout::{Direction, LayoutInfo},
   rect::Rect,
   signal::{Signal, SignalStore},
   state::StateRepo,
+  time::TimeInfo,
 };
 
+/// Paint commands and bounds of a single UI root, collected during the current frame
+struct RootLayer {
+  z: i32,
+  order: usize,
+  kind: LayerKind,
+  bounds: Rect,
+  paint: PaintList,
+  overlay: PaintList,
+  overlay_occluders: Range<usize>,
+}
+
+/// Bounds of a UI root from the previous frame, used to block the input to the layers below
+#[derive(Clone)]
+struct LayerBounds {
+  z: i32,
+  order: usize,
+  kind: LayerKind,
+  bounds: Rect,
+  /// Range of `prev_overlay_occluders` registered by the elements of this root
+  overlay_occluders: Range<usize>,
+}
+
 /// The main instance of the UI system.
 ///
 /// In most cases, you should only have one instance of this struct, but multiple instances are allowed\
@@ -21,11 +47,17 @@ pub struct UiInstance {
   painter: PainterInstance,
   presentation: Presentatation,
   paint_commands: PaintList,
+  layers: Vec<RootLayer>,
+  prev_layers: Vec<LayerBounds>,
+  occluders: Vec<Rect>,
+  overlay_occluders: Vec<Rect>,
+  prev_overlay_occluders: Vec<Rect>,
   stateful_state: StateRepo,
   events: EventQueue,
   input: UiInputState,
   signal: SignalStore,
   font_stack: FontStack,
+  time: TimeInfo,
 }
 
 impl UiInstance {
@@ -37,11 +69,17 @@ impl UiInstance {
       painter: PainterInstance::new(),
       presentation: Presentatation::new(),
       paint_commands: PaintList::default(),
+      layers: Vec::new(),
+      prev_layers: Vec::new(),
+      occluders: Vec::new(),
+      overlay_occluders: Vec::new(),
+      prev_overlay_occluders: Vec::new(),
       font_stack: FontStack::new(),
       stateful_state: StateRepo::new(),
       events: EventQueue::new(),
       input: UiInputState::new(),
       signal: SignalStore::new(),
+      time: TimeInfo::default(),
     }
   }
 
@@ -110,7 +148,54 @@ impl UiInstance {
   /// ## Panics:
   /// If called while the UI is not active (call [`UiInstance::begin`] first)
   pub fn add(&mut self, element: impl UiElement, rect: impl Into<Rect>) {
-    let rect: Rect = rect.into();
+    self.add_root(&element, rect.into(), None);
+  }
+
+  /// Add an element or an element tree to the UI, on the specified layer
+  ///
+  /// Same as [`UiInstance::add`], but allows placing the root above (or below) other roots,
+  /// and blocking the input to the layers below it (see [`Layer`] for more details)
+  ///
+  /// ## Panics:
+  /// If called while the UI is not active (call [`UiInstance::begin`] first)
+  pub fn add_layer(&mut self, layer: Layer, element: impl UiElement, rect: impl Into<Rect>) {
+    self.add_root(&element, rect.into(), Some(&layer));
+  }
+
+  fn add_root(&mut self, element: &dyn UiElement, rect: Rect, layer: Option<&Layer>) {
+    let z = layer.map_or(0, |layer| layer.z);
+    let kind = layer.map_or(LayerKind::Normal, |layer| layer.kind);
+    let order = self.layers.len();
+
+    //Block the input covered by the layers above (based on the previous frame)
+    //Modals and popups are also above the roots with the same z that were added before them
+    self.occluders.clear();
+    let mut blocked = false;
+    let mut keyboard_blocked = false;
+    for above in &self.prev_layers {
+      let drawn_above = (above.z, above.order) > (z, order);
+      match above.kind {
+        LayerKind::Normal if above.z > z => self.occluders.push(above.bounds),
+        LayerKind::Modal if drawn_above => blocked = true,
+        LayerKind::Popup if drawn_above => {
+          self.occluders.push(above.bounds);
+          keyboard_blocked = true;
+        },
+        _ => (),
+      }
+      //Open popups drawn into the overlay of a root block the input to it and to the layers below it
+      if (above.z, above.order) >= (z, order) {
+        self.occluders.extend_from_slice(&self.prev_overlay_occluders[above.overlay_occluders.clone()]);
+      }
+    }
+    let input = self.input.ctx().with_occluders(&self.occluders, blocked, keyboard_blocked);
+
+    let mut paint = PaintList::new_empty();
+    let mut overlay = PaintList::new_empty();
+    if let Some(layer) = layer.filter(|layer| layer.kind == LayerKind::Modal) {
+      layer.dim.draw(&mut paint, rect);
+    }
+
     let layout = LayoutInfo {
       position: rect.position,
       max_size: rect.size,
@@ -125,21 +210,62 @@ impl UiInstance {
       layout: &layout,
       current_font,
     });
+    let overlay_occluders_start = self.overlay_occluders.len();
     element.process(ProcessContext {
       painter: &mut self.painter,
       measure: &measure,
       state: &mut self.stateful_state,
       layout: &layout,
-      paint_target: &mut self.paint_commands,
-      input: self.input.ctx(),
+      paint_target: &mut paint,
+      paint_overlay: &mut overlay,
+      input,
       signal: &mut self.signal,
+      time: self.time,
+      root_rect: rect,
+      overlay_occluders: &mut self.overlay_occluders,
       current_font,
     });
+
+    //Modal layers cover the entire root rect
+    let bounds = match kind {
+      LayerKind::Modal => rect,
+      LayerKind::Normal | LayerKind::Popup => measure.rect(rect.position),
+    };
+
+    //Handle close requests
+    //(keyboard input is blocked by the modals and popups above, so only the topmost one is closed by the Escape key)
+    if let Some(signal) = layer.and_then(|layer| layer.on_close.as_ref()) {
+      let escape = kind != LayerKind::Normal && input.keys_pressed().any(|key| key == KeyboardKey::Escape);
+      let outside_click = kind == LayerKind::Popup &&
+        input.mouse_button_just_pressed(MouseButton::Primary) &&
+        !bounds.contains_point(input.mouse_position());
+      if escape || outside_click {
+        signal.fire(&mut self.signal);
+      }
+    }
+
+    let overlay_occluders = overlay_occluders_start..self.overlay_occluders.len();
+    self.layers.push(RootLayer { z, order, kind, bounds, paint, overlay, overlay_occluders });
+  }
+
+  /// Advance the UI clock by the given amount of time (in seconds)
+  ///
+  /// Should be called once per frame, before [`UiInstance::begin_frame`]\
+  /// Elements that rely on time (delays, animations, etc.) will not progress if this function is never called
+  pub fn advance_time(&mut self, delta_time: f32) {
+    self.time.delta = delta_time;
+    self.time.elapsed += delta_time;
+  }
+
+  /// Get the timing information of the current frame
+  pub fn time(&self) -> TimeInfo {
+    self.time
   }
 
   /// Reset the state from the previous frame, and prepare the UI for layout and processing
   ///
   /// - You must call this function at the start of the frame, before adding any elements
+  /// - Call [`UiInstance::advance_time`] before this function to update the frame timing information
   /// - Make sure to provide all of the events that happened since the last frame before calling this function, to avoid a 1-frame delay in event processing
   pub fn begin_frame(&mut self) {
     //first, drain and process the event queue
@@ -150,12 +276,31 @@ impl UiInstance {
 
     // Clear the draw commands
     self.paint_commands.clear();
+    self.layers.clear();
   }
 
   /// End rendering the current frame and present it
   ///
   /// You must call this function sometime at the end of the frame, after adding all elements but before rendering, but before running the render backend
   pub fn end_frame(&mut self) {
+    //Draw the layers in z-order (stable sort preserves the order in which the roots were added)
+    //Overlay of each layer (popups, etc.) is drawn on top of it, but below the layers above it
+    self.layers.sort_by_key(|layer| layer.z);
+    self.prev_layers.clear();
+    for layer in &mut self.layers {
+      self.paint_commands.commands.append(&mut layer.paint.commands);
+      self.paint_commands.commands.append(&mut layer.overlay.commands);
+      self.prev_layers.push(LayerBounds {
+        z: layer.z,
+        order: layer.order,
+        kind: layer.kind,
+        bounds: layer.bounds,
+        overlay_occluders: layer.overlay_occluders.clone(),
+      });
+    }
+    core::mem::swap(&mut self.prev_overlay_occluders, &mut self.overlay_occluders);
+    self.overlay_occluders.clear();
+    self.layers.clear();
     self.presentation.draw(&mut self.painter, &self.paint_commands);
   }
 
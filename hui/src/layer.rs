@@ -0,0 +1,104 @@
+//! layers, allowing ui roots to be stacked on top of each other (modals, popups, etc.)
+
+use alloc::boxed::Box;
+use crate::{
+  frame::{Frame, RectFrame},
+  signal::{trigger::SignalTrigger, Signal},
+};
+
+/// Controls how a layer interacts with the layers below it
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum LayerKind {
+  /// Regular layer\
+  /// Blocks the pointer input to the layers below, but only within its own bounds
+  #[default]
+  Normal,
+
+  /// Modal layer\
+  /// Dims the layers below, and blocks all of the input that would otherwise reach them\
+  /// Can be closed with the Escape key
+  Modal,
+
+  /// Popup layer\
+  /// Blocks the pointer input to the layers below within its own bounds, and all of the keyboard input\
+  /// Can be closed with the Escape key or by clicking anywhere outside of it
+  Popup,
+}
+
+/// Parameters of a UI layer
+///
+/// Layers are drawn in the order of their `z` value (lowest first),
+/// roots with the same `z` value are drawn in the order they were added\
+/// Roots added using [`UiInstance::add`](crate::UiInstance::add) are placed on the layer with `z = 0`
+///
+/// Please note that input blocking is based on the layers present in the previous frame
+pub struct Layer {
+  /// Z-order of the layer, higher values are drawn on top
+  pub z: i32,
+
+  /// Kind of the layer
+  pub kind: LayerKind,
+
+  /// Frame drawn over the entire root rect before the contents of a modal layer (to dim the layers below)
+  ///
+  /// Only used for [`LayerKind::Modal`] layers
+  pub dim: Box<dyn Frame>,
+
+  /// Signal that will be fired when the user requests the layer to be closed\
+  /// (using the Escape key, or by clicking outside of a popup layer)\
+  /// Only the topmost modal or popup layer receives the Escape key
+  ///
+  /// Layers are not closed automatically, stop adding the layer in response to this signal
+  pub on_close: Option<SignalTrigger>,
+}
+
+impl Default for Layer {
+  fn default() -> Self {
+    Self {
+      z: 0,
+      kind: LayerKind::Normal,
+      dim: Box::new(RectFrame::color((0., 0., 0., 0.5))),
+      on_close: None,
+    }
+  }
+}
+
+impl Layer {
+  /// Create a new regular layer
+  pub fn new(z: i32) -> Self {
+    Self {
+      z,
+      ..Default::default()
+    }
+  }
+
+  /// Create a new modal layer
+  pub fn modal(z: i32) -> Self {
+    Self {
+      z,
+      kind: LayerKind::Modal,
+      ..Default::default()
+    }
+  }
+
+  /// Create a new popup layer
+  pub fn popup(z: i32) -> Self {
+    Self {
+      z,
+      kind: LayerKind::Popup,
+      ..Default::default()
+    }
+  }
+
+  pub fn with_dim(mut self, frame: impl Frame + 'static) -> Self {
+    self.dim = Box::new(frame);
+    self
+  }
+
+  pub fn on_close<S: Signal, F: Fn() -> S + 'static>(self, f: F) -> Self {
+    Self {
+      on_close: Some(SignalTrigger::new(f)),
+      ..self
+    }
+  }
+}
@@ -58,6 +58,18 @@ pub enum Alignment {
   End = 2,
 }
 
+impl Alignment {
+  /// Get the offset of an element of the given size within the available space
+  #[inline]
+  pub fn offset(self, available: f32, size: f32) -> f32 {
+    match self {
+      Self::Begin => 0.,
+      Self::Center => (available - size) / 2.,
+      Self::End => available - size,
+    }
+  }
+}
+
 /// Represents alignment in 2D space
 ///
 /// - `horizontal` - alignment *along* x-axis (horizontal)\
@@ -94,6 +106,15 @@ impl Alignment2d {
       vertical: alignment,
     }
   }
+
+  /// Get the offset of an element of the given size within the available space
+  #[inline]
+  pub fn offset(self, available: Vec2, size: Vec2) -> Vec2 {
+    vec2(
+      self.horizontal.offset(available.x, size.x),
+      self.vertical.offset(available.y, size.y),
+    )
+  }
 }
 
 impl From<(Alignment, Alignment)> for Alignment2d {
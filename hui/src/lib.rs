@@ -28,5 +28,7 @@ pub mod state;
 pub mod signal;
 pub mod frame;
 pub mod font;
+pub mod time;
+pub mod layer;
 
 pub use instance::UiInstance;
@@ -15,7 +15,14 @@ pub use hui_derive::State;
 pub trait State: Any {}
 
 /// Integer type used to identify a state object
-type StateId = u64;
+pub type StateId = u64;
+
+/// Compute a [`StateId`] from any hashable value
+///
+/// Useful for stateful elements, which need to store their id without being generic over its type
+pub fn state_id(x: impl Hash) -> StateId {
+  hash_global(x)
+}
 
 fn hash_local(x: impl Hash, g: &[StateId]) -> StateId {
   let mut hasher = FxHasher::default();
@@ -75,6 +82,25 @@ impl StateRepo {
     self.state.get(&id).unwrap().downcast_ref::<T>()
   }
 
+  /// Get a reference to a state object by its id, without marking it as active
+  ///
+  /// Unlike [`StateRepo::acquire`], this does not require mutable access, so it can be used during the measure step
+  pub fn peek<T: State>(&self, id: impl Hash) -> Option<&T> {
+    let id = hash_local(id, &self.id_stack);
+    self.state.get(&id)?.downcast_ref::<T>()
+  }
+
+  /// Get a reference to a state object by its id, as if each of the ids in `path` was pushed to the stack first
+  ///
+  /// Same as [`StateRepo::peek`], useful for peeking into nested state during the measure step
+  pub fn peek_nested<T: State>(&self, path: &[impl Hash], id: impl Hash) -> Option<&T> {
+    let stack: Vec<StateId> = self.id_stack.iter().copied()
+      .chain(path.iter().map(hash_global))
+      .collect();
+    let id = hash_local(id, &stack);
+    self.state.get(&id)?.downcast_ref::<T>()
+  }
+
   /// Get a reference to a state object by its id or insert a new one
   pub fn acquire_or_insert<T: State>(&mut self, id: impl Hash, state: T) -> &T {
     let id = hash_local(id, &self.id_stack);
@@ -0,0 +1,13 @@
+//! frame timing information, used for delays and animations
+
+/// Timing information of the current frame
+///
+/// Updated by [`UiInstance::advance_time`](crate::UiInstance::advance_time)
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct TimeInfo {
+  /// Total time elapsed since the creation of the UI instance, in seconds
+  pub elapsed: f32,
+
+  /// Time elapsed since the previous frame, in seconds
+  pub delta: f32,
+}